@@ -0,0 +1,416 @@
+use std::cell::RefMut;
+
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Allocate, Assign, CreateAccount, Transfer},
+};
+use anchor_spl::{associated_token::get_associated_token_address, token};
+
+use crate::{
+    constants::BONDING_CURVE_SEED,
+    error::PumpCloneError,
+    events::{
+        AccountUpgraded, AllowlistCleared, ConfigChangeCancelled, FeesWithdrawn, WalletBlocked,
+        WalletUnblocked, EVENT_VERSION,
+    },
+    instructions::{complete_bonding_curve, upgrade_account::upgrade_target, withdraw},
+    oracle::sol_usd_price,
+    state::{AdminAction, BlockedWallet, BondingCurve, GlobalState, PendingConfigChange},
+    utils::{close_account, load_account},
+};
+
+/// Accounts an `AdminAction` can touch besides `GlobalState`, as received by
+/// whichever executor (the admin multisig or governance) is running it.
+pub struct AdminActionAccounts<'info> {
+    /// PDA acting as the platform authority
+    pub authority: Pubkey,
+    /// Pays rent for accounts an action creates or grows and receives the
+    /// rent of accounts it closes
+    pub payer: AccountInfo<'info>,
+    pub fee_vault: AccountInfo<'info>,
+    pub fee_vault_bump: u8,
+    /// Destination of `WithdrawFees`, and of the SOL a `Withdraw` pays out,
+    /// which must be the platform's fee recipient
+    pub recipient: AccountInfo<'info>,
+    /// The blocklist PDA, curve, pending config change or account to upgrade
+    /// the action operates on
+    pub target: Option<AccountInfo<'info>>,
+    /// Price feed `CompleteBondingCurve` values the curve with
+    pub oracle_feed: Option<AccountInfo<'info>>,
+    /// Token accounts `Withdraw` moves the curve's tokens between: the
+    /// curve's and the recipient's associated token accounts
+    pub bonding_curve_token_account: Option<AccountInfo<'info>>,
+    pub recipient_token_account: Option<AccountInfo<'info>>,
+    pub token_program: Option<AccountInfo<'info>>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> AdminActionAccounts<'info> {
+    fn target(&self) -> Result<&AccountInfo<'info>> {
+        required(&self.target)
+    }
+}
+
+fn required<'a, 'info>(
+    account: &'a Option<AccountInfo<'info>>,
+) -> Result<&'a AccountInfo<'info>> {
+    account.as_ref().ok_or(error!(PumpCloneError::InvalidAccount))
+}
+
+/// Runs `action` on behalf of `accounts.authority`. Only `AcceptAuthority`
+/// can be run by the pending authority; everything else requires the
+/// executor to already hold the platform authority. `proposed_at` starts the
+/// config timelock for `UpdateConfig`. `SetSigners` belongs to the multisig
+/// and is rejected here.
+pub fn execute(
+    global_state: &mut GlobalState,
+    action: &AdminAction,
+    accounts: &AdminActionAccounts,
+    proposed_at: i64,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    match action {
+        AdminAction::AcceptAuthority => require!(
+            global_state.pending_authority == Some(accounts.authority),
+            PumpCloneError::UnauthorizedAdmin
+        ),
+        _ => require!(
+            global_state.authority == accounts.authority,
+            PumpCloneError::UnauthorizedAdmin
+        ),
+    }
+
+    match action {
+        AdminAction::UpdateConfig(update) => {
+            // Config changes wait out the same timelock as queued changes,
+            // counted from when the proposal was published
+            let eta = proposed_at
+                .checked_add(global_state.config_timelock)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?;
+            require!(timestamp >= eta, PumpCloneError::TimelockPeriodNotElapsed);
+
+            update.validate()?;
+            update.apply(global_state);
+        }
+        AdminAction::WithdrawFees { amount, recipient } => {
+            require!(
+                !global_state.is_paused(GlobalState::PAUSE_WITHDRAW),
+                PumpCloneError::EmergencyPauseActivated
+            );
+            require_keys_eq!(accounts.recipient.key(), *recipient, PumpCloneError::InvalidAccount);
+
+            let available = accounts.fee_vault
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            require!(*amount <= available, PumpCloneError::InsufficientFunds);
+
+            let seeds = &[b"fee_vault".as_ref(), &[accounts.fee_vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    accounts.system_program.clone(),
+                    Transfer {
+                        from: accounts.fee_vault.clone(),
+                        to: accounts.recipient.clone(),
+                    },
+                    signer_seeds,
+                ),
+                *amount,
+            )?;
+
+            emit!(FeesWithdrawn {
+                version: EVENT_VERSION,
+                recipient: *recipient,
+                amount: *amount,
+                authorized_by: accounts.authority,
+                timestamp,
+            });
+        }
+        AdminAction::ProposeAuthority { new_authority } => {
+            require!(
+                *new_authority != Pubkey::default() && *new_authority != accounts.authority,
+                PumpCloneError::InvalidConfigurationParams
+            );
+            global_state.pending_authority = Some(*new_authority);
+        }
+        AdminAction::AcceptAuthority => {
+            global_state.authority = accounts.authority;
+            global_state.pending_authority = None;
+        }
+        AdminAction::SetFeeRecipient { fee_recipient } => {
+            require!(
+                *fee_recipient != Pubkey::default(),
+                PumpCloneError::InvalidFeeRecipient
+            );
+            global_state.fee_recipient = *fee_recipient;
+        }
+        AdminAction::SetGuardian { guardian } => {
+            global_state.guardian = *guardian;
+        }
+        AdminAction::Unpause { scopes } => {
+            global_state.pause_flags &= !scopes;
+        }
+        AdminAction::BlockWallet { wallet, reason } => {
+            let target = accounts.target()?;
+            let (address, bump) =
+                Pubkey::find_program_address(&[b"blocked_wallet", wallet.as_ref()], &crate::ID);
+            require_keys_eq!(target.key(), address, PumpCloneError::InvalidAccount);
+
+            create_pda(
+                target,
+                accounts,
+                BlockedWallet::LEN,
+                &[b"blocked_wallet", wallet.as_ref(), &[bump]],
+            )?;
+
+            let blocked_wallet = BlockedWallet {
+                wallet: *wallet,
+                blocked_by: accounts.authority,
+                reason: reason.clone(),
+                blocked_at: timestamp,
+                bump,
+            };
+            blocked_wallet.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+
+            emit!(WalletBlocked {
+                version: EVENT_VERSION,
+                wallet: *wallet,
+                authority: accounts.authority,
+                reason: reason.clone(),
+                timestamp,
+            });
+        }
+        AdminAction::UnblockWallet { wallet } => {
+            let target = accounts.target()?;
+            let blocked_wallet: BlockedWallet = load_account(target)?;
+            require_keys_eq!(blocked_wallet.wallet, *wallet, PumpCloneError::InvalidAccount);
+
+            let address = Pubkey::create_program_address(
+                &[b"blocked_wallet", wallet.as_ref(), &[blocked_wallet.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(PumpCloneError::InvalidAccount))?;
+            require_keys_eq!(target.key(), address, PumpCloneError::InvalidAccount);
+
+            close_account(target, &accounts.payer)?;
+
+            emit!(WalletUnblocked {
+                version: EVENT_VERSION,
+                wallet: *wallet,
+                authority: accounts.authority,
+                timestamp,
+            });
+        }
+        AdminAction::ClearAllowlist { mint } => {
+            let mut bonding_curve = load_bonding_curve(accounts.target()?, mint)?;
+            bonding_curve.allowlist_root = [0; 32];
+
+            emit!(AllowlistCleared {
+                version: EVENT_VERSION,
+                mint: *mint,
+                cleared_by: accounts.authority,
+                timestamp,
+            });
+        }
+        AdminAction::CancelConfigChange { index } => {
+            let target = accounts.target()?;
+            let pending: PendingConfigChange = load_account(target)?;
+            require!(pending.index == *index, PumpCloneError::InvalidAccount);
+
+            let address = Pubkey::create_program_address(
+                &[b"config_change", &index.to_le_bytes(), &[pending.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(PumpCloneError::InvalidAccount))?;
+            require_keys_eq!(target.key(), address, PumpCloneError::InvalidAccount);
+
+            close_account(target, &accounts.payer)?;
+
+            emit!(ConfigChangeCancelled {
+                version: EVENT_VERSION,
+                index: *index,
+                authority: accounts.authority,
+                timestamp,
+            });
+        }
+        AdminAction::UpgradeAccount { kind } => {
+            let target = accounts.target()?;
+            let (from_version, to_version) =
+                upgrade_target(*kind, target, &accounts.payer, &accounts.system_program)?;
+
+            emit!(AccountUpgraded {
+                version: EVENT_VERSION,
+                account: target.key(),
+                kind: *kind,
+                from_version,
+                to_version,
+                authority: accounts.authority,
+                timestamp,
+            });
+        }
+        AdminAction::CompleteBondingCurve { mint } => {
+            require!(
+                !global_state.is_paused(GlobalState::PAUSE_MIGRATE),
+                PumpCloneError::EmergencyPauseActivated
+            );
+
+            let oracle_feed = required(&accounts.oracle_feed)?;
+            require_keys_eq!(
+                oracle_feed.key(),
+                global_state.oracle_feed,
+                PumpCloneError::InvalidPriceFeed
+            );
+            let sol_usd_price = sol_usd_price(oracle_feed, global_state)?;
+
+            let mut bonding_curve = load_bonding_curve(accounts.target()?, mint)?;
+            let event =
+                complete_bonding_curve::complete(&mut bonding_curve, global_state, sol_usd_price)?;
+            drop(bonding_curve);
+
+            emit!(event);
+        }
+        AdminAction::Withdraw { mint } => {
+            require!(
+                !global_state.is_paused(GlobalState::PAUSE_WITHDRAW),
+                PumpCloneError::EmergencyPauseActivated
+            );
+            require_keys_eq!(
+                accounts.recipient.key(),
+                global_state.fee_recipient,
+                PumpCloneError::InvalidFeeRecipient
+            );
+
+            let target = accounts.target()?;
+            drop(load_bonding_curve(target, mint)?);
+
+            let bonding_curve_token_account = required(&accounts.bonding_curve_token_account)?;
+            require_keys_eq!(
+                bonding_curve_token_account.key(),
+                get_associated_token_address(&target.key(), mint),
+                PumpCloneError::InvalidAssociatedTokenAccount
+            );
+            let recipient_token_account = required(&accounts.recipient_token_account)?;
+            require_keys_eq!(
+                recipient_token_account.key(),
+                get_associated_token_address(&accounts.recipient.key(), mint),
+                PumpCloneError::InvalidAssociatedTokenAccount
+            );
+            let token_program = required(&accounts.token_program)?;
+            require_keys_eq!(token_program.key(), token::ID, PumpCloneError::TokenProgramMismatch);
+
+            let event = withdraw::migrate(
+                global_state,
+                target,
+                bonding_curve_token_account,
+                &accounts.recipient,
+                recipient_token_account,
+                &accounts.fee_vault,
+                token_program,
+            )?;
+
+            emit!(event);
+        }
+        AdminAction::SetSigners { .. } => {
+            return err!(PumpCloneError::UnsupportedAdminAction);
+        }
+    }
+
+    Ok(())
+}
+
+/// Borrows `target` as the bonding curve of `mint`, after checking it is
+/// one: owned by this program, with the curve's discriminator, recording
+/// `mint` and at the curve PDA's address.
+fn load_bonding_curve<'a>(
+    target: &'a AccountInfo,
+    mint: &Pubkey,
+) -> Result<RefMut<'a, BondingCurve>> {
+    require_keys_eq!(*target.owner, crate::ID, PumpCloneError::InvalidAccount);
+
+    let data = target.try_borrow_mut_data()?;
+    require!(
+        data.len() >= BondingCurve::LEN
+            && data[..8] == <BondingCurve as anchor_lang::Discriminator>::DISCRIMINATOR,
+        PumpCloneError::InvalidAccount
+    );
+    let bonding_curve: RefMut<BondingCurve> =
+        RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[8..BondingCurve::LEN]));
+    require_keys_eq!(bonding_curve.mint, *mint, PumpCloneError::InvalidAccount);
+
+    let address = Pubkey::create_program_address(
+        &[BONDING_CURVE_SEED, mint.as_ref(), &[bonding_curve.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(PumpCloneError::InvalidAccount))?;
+    require_keys_eq!(target.key(), address, PumpCloneError::InvalidAccount);
+
+    Ok(bonding_curve)
+}
+
+/// Creates a PDA owned by this program the way `init` does, including when
+/// its address has already been sent lamports.
+fn create_pda<'info>(
+    target: &AccountInfo<'info>,
+    accounts: &AdminActionAccounts<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    require!(target.data_is_empty(), PumpCloneError::AccountAlreadyInitialized);
+
+    let signer_seeds = &[seeds];
+    let rent = Rent::get()?.minimum_balance(space);
+
+    if target.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                accounts.system_program.clone(),
+                CreateAccount {
+                    from: accounts.payer.clone(),
+                    to: target.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(target.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.clone(),
+                Transfer {
+                    from: accounts.payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            accounts.system_program.clone(),
+            Allocate {
+                account_to_allocate: target.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+
+    system_program::assign(
+        CpiContext::new_with_signer(
+            accounts.system_program.clone(),
+            Assign {
+                account_to_assign: target.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use crate::state::TokenMetadata;
+
+pub const BONDING_CURVE_SEED: &[u8] = b"bonding_curve";
+
+pub const TOKEN_DECIMALS: u8 = 6;
+
+/// Fixed supply of every launched mint, in base units.
+pub const TOTAL_SUPPLY: u64 = 1_000_000_000 * 10u64.pow(TOKEN_DECIMALS as u32);
+
+pub const MAX_NAME_LENGTH: usize = TokenMetadata::MAX_NAME_LEN;
+pub const MAX_SYMBOL_LENGTH: usize = TokenMetadata::MAX_SYMBOL_LEN;
+pub const MAX_URI_LENGTH: usize = TokenMetadata::MAX_URI_LEN;
\ No newline at end of file
@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, state::BondingCurve};
+
+const BPS_DENOMINATOR: u128 = 10000;
+
+/// Reserves the constant-product curve prices against. Virtual reserves set
+/// the price; real token reserves cap how much can still be bought before the
+/// curve completes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct CurveReserves {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+impl From<&BondingCurve> for CurveReserves {
+    fn from(bonding_curve: &BondingCurve) -> Self {
+        Self {
+            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: bonding_curve.virtual_token_reserves,
+            real_sol_reserves: bonding_curve.real_sol_reserves,
+            real_token_reserves: bonding_curve.real_token_reserves,
+        }
+    }
+}
+
+/// Returned by `quote_buy` and `quote_buy_exact_out`. `sol_amount` is what
+/// the buyer pays including `fee_amount`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct BuyQuote {
+    pub sol_amount: u64,
+    pub fee_amount: u64,
+    pub token_amount: u64,
+    pub completes_curve: bool,
+    pub reserves_after: CurveReserves,
+}
+
+/// Returned by `quote_sell`. `net_sol_amount` is what the seller receives.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SellQuote {
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub fee_amount: u64,
+    pub net_sol_amount: u64,
+    pub reserves_after: CurveReserves,
+}
+
+/// Returned by `get_curve_state`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct CurveState {
+    pub mint: Pubkey,
+    pub reserves: CurveReserves,
+    pub token_total_supply: u64,
+    pub spot_price: u64,
+    pub market_cap: u64,
+    pub fee_bps: u16,
+    pub complete: bool,
+    pub presale_pending: bool,
+}
+
+/// Tokens received for `sol_in` lamports entering the curve (after fees),
+/// rounded down.
+pub fn tokens_out_for_sol(reserves: &CurveReserves, sol_in: u64) -> Result<u64> {
+    let numerator = (reserves.virtual_token_reserves as u128)
+        .checked_mul(sol_in as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    let denominator = (reserves.virtual_sol_reserves as u128)
+        .checked_add(sol_in as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    require!(denominator > 0, PumpCloneError::DivisionByZero);
+
+    to_u64(numerator / denominator)
+}
+
+/// Lamports that must enter the curve (after fees) to take out `tokens_out`,
+/// rounded up.
+pub fn sol_in_for_tokens(reserves: &CurveReserves, tokens_out: u64) -> Result<u64> {
+    require!(
+        tokens_out < reserves.virtual_token_reserves,
+        PumpCloneError::InsufficientTokensForSale
+    );
+
+    let numerator = (reserves.virtual_sol_reserves as u128)
+        .checked_mul(tokens_out as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    let denominator = (reserves.virtual_token_reserves - tokens_out) as u128;
+
+    to_u64(numerator.div_ceil(denominator))
+}
+
+/// Lamports released by the curve (before fees) for `tokens_in`, rounded
+/// down.
+pub fn sol_out_for_tokens(reserves: &CurveReserves, tokens_in: u64) -> Result<u64> {
+    let numerator = (reserves.virtual_sol_reserves as u128)
+        .checked_mul(tokens_in as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    let denominator = (reserves.virtual_token_reserves as u128)
+        .checked_add(tokens_in as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    require!(denominator > 0, PumpCloneError::DivisionByZero);
+
+    to_u64(numerator / denominator)
+}
+
+/// Fee taken out of `amount`, rounded down.
+pub fn fee_amount(amount: u64, fee_bps: u16) -> Result<u64> {
+    to_u64((amount as u128) * (fee_bps as u128) / BPS_DENOMINATOR)
+}
+
+/// Smallest gross amount whose fee-exclusive part is at least `net`.
+pub fn gross_up(net: u64, fee_bps: u16) -> Result<u64> {
+    let remaining_bps = BPS_DENOMINATOR
+        .checked_sub(fee_bps as u128)
+        .filter(|bps| *bps > 0)
+        .ok_or(PumpCloneError::InvalidConfigurationParams)?;
+
+    if net == 0 {
+        return Ok(0);
+    }
+
+    // The fee is rounded down, so `gross` keeps
+    // ceil(gross * remaining_bps / BPS_DENOMINATOR) after it, which reaches
+    // `net` once gross * remaining_bps exceeds (net - 1) * BPS_DENOMINATOR
+    to_u64((net as u128 - 1) * BPS_DENOMINATOR / remaining_bps + 1)
+}
+
+/// Quote for spending `sol_amount` lamports including fees. A buy that would
+/// take more than the remaining real reserves is clipped to exactly those
+/// reserves and charged only what they cost.
+pub fn quote_buy(reserves: &CurveReserves, sol_amount: u64, fee_bps: u16) -> Result<BuyQuote> {
+    require!(sol_amount > 0, PumpCloneError::InvalidPurchaseAmount);
+
+    let fee = fee_amount(sol_amount, fee_bps)?;
+    let net_sol = sol_amount - fee;
+    let token_amount = tokens_out_for_sol(reserves, net_sol)?;
+
+    if token_amount >= reserves.real_token_reserves {
+        return quote_buy_exact_out(reserves, reserves.real_token_reserves, fee_bps);
+    }
+
+    require!(token_amount > 0, PumpCloneError::InvalidPurchaseAmount);
+    buy_quote(reserves, sol_amount, fee, net_sol, token_amount)
+}
+
+/// Quote for receiving exactly `token_amount` tokens, clipped to the
+/// remaining real reserves.
+pub fn quote_buy_exact_out(
+    reserves: &CurveReserves,
+    token_amount: u64,
+    fee_bps: u16,
+) -> Result<BuyQuote> {
+    let token_amount = token_amount.min(reserves.real_token_reserves);
+    require!(token_amount > 0, PumpCloneError::InsufficientTokensForSale);
+
+    let net_sol = sol_in_for_tokens(reserves, token_amount)?;
+    let sol_amount = gross_up(net_sol, fee_bps)?;
+
+    buy_quote(reserves, sol_amount, sol_amount - net_sol, net_sol, token_amount)
+}
+
+/// Quote for selling `token_amount` tokens back to the curve.
+pub fn quote_sell(reserves: &CurveReserves, token_amount: u64, fee_bps: u16) -> Result<SellQuote> {
+    require!(token_amount > 0, PumpCloneError::InvalidAmount);
+
+    let sol_amount = sol_out_for_tokens(reserves, token_amount)?;
+    require!(
+        sol_amount <= reserves.real_sol_reserves,
+        PumpCloneError::InsufficientFunds
+    );
+
+    let fee = fee_amount(sol_amount, fee_bps)?;
+
+    Ok(SellQuote {
+        token_amount,
+        sol_amount,
+        fee_amount: fee,
+        net_sol_amount: sol_amount - fee,
+        reserves_after: CurveReserves {
+            virtual_sol_reserves: reserves.virtual_sol_reserves - sol_amount,
+            virtual_token_reserves: reserves
+                .virtual_token_reserves
+                .checked_add(token_amount)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?,
+            real_sol_reserves: reserves.real_sol_reserves - sol_amount,
+            real_token_reserves: reserves
+                .real_token_reserves
+                .checked_add(token_amount)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?,
+        },
+    })
+}
+
+fn buy_quote(
+    reserves: &CurveReserves,
+    sol_amount: u64,
+    fee: u64,
+    net_sol: u64,
+    token_amount: u64,
+) -> Result<BuyQuote> {
+    let reserves_after = CurveReserves {
+        virtual_sol_reserves: reserves
+            .virtual_sol_reserves
+            .checked_add(net_sol)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?,
+        virtual_token_reserves: reserves.virtual_token_reserves - token_amount,
+        real_sol_reserves: reserves
+            .real_sol_reserves
+            .checked_add(net_sol)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?,
+        real_token_reserves: reserves.real_token_reserves - token_amount,
+    };
+
+    Ok(BuyQuote {
+        sol_amount,
+        fee_amount: fee,
+        token_amount,
+        completes_curve: reserves_after.real_token_reserves == 0,
+        reserves_after,
+    })
+}
+
+fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEE_BPS: u16 = 100;
+
+    /// A fresh launch on the default platform curve.
+    fn launch_reserves() -> CurveReserves {
+        CurveReserves {
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 793_100_000_000_000,
+        }
+    }
+
+    fn invariant(reserves: &CurveReserves) -> u128 {
+        reserves.virtual_sol_reserves as u128 * reserves.virtual_token_reserves as u128
+    }
+
+    #[test]
+    fn swaps_round_in_the_curves_favour() {
+        let reserves = CurveReserves {
+            virtual_sol_reserves: 3,
+            virtual_token_reserves: 10,
+            ..Default::default()
+        };
+
+        // 10 * 1 / 4 = 2.5 tokens out
+        assert_eq!(tokens_out_for_sol(&reserves, 1).unwrap(), 2);
+        // 3 * 2 / 8 = 0.75 lamports in
+        assert_eq!(sol_in_for_tokens(&reserves, 2).unwrap(), 1);
+        // 3 * 1 / 11 = 0.27 lamports out
+        assert_eq!(sol_out_for_tokens(&reserves, 1).unwrap(), 0);
+        // 1% of 999 = 9.99 lamports of fee
+        assert_eq!(fee_amount(999, FEE_BPS).unwrap(), 9);
+    }
+
+    #[test]
+    fn gross_up_covers_the_fee_taken_from_it() {
+        for net in [1, 99, 100, 101, 12_345, 1_000_000_007] {
+            let gross = gross_up(net, FEE_BPS).unwrap();
+            assert!(gross - fee_amount(gross, FEE_BPS).unwrap() >= net);
+            // One lamport less would not
+            assert!(gross - 1 - fee_amount(gross - 1, FEE_BPS).unwrap() < net);
+        }
+
+        assert_eq!(gross_up(100, 0).unwrap(), 100);
+        assert!(gross_up(100, 10000).is_err());
+    }
+
+    #[test]
+    fn exact_out_quote_buys_at_least_the_tokens_asked_for() {
+        let reserves = launch_reserves();
+
+        for tokens in [1, 1_000, 35_000_000_000, 400_000_000_000_000] {
+            let exact_out = quote_buy_exact_out(&reserves, tokens, FEE_BPS).unwrap();
+            assert_eq!(exact_out.token_amount, tokens);
+
+            // Paying the quoted amount through a regular buy gets the tokens
+            let buy = quote_buy(&reserves, exact_out.sol_amount, FEE_BPS).unwrap();
+            assert!(buy.token_amount >= tokens);
+            // and the quote is the cheapest amount that does
+            let cheaper = exact_out.sol_amount - 1;
+            let short = quote_buy(&reserves, cheaper, FEE_BPS).map(|quote| quote.token_amount);
+            assert!(short.map_or(true, |amount| amount < tokens));
+        }
+    }
+
+    #[test]
+    fn buy_beyond_the_real_reserves_is_clipped_to_them() {
+        let reserves = launch_reserves();
+
+        let quote = quote_buy(&reserves, 1_000_000_000_000, FEE_BPS).unwrap();
+        let exact_out =
+            quote_buy_exact_out(&reserves, reserves.real_token_reserves, FEE_BPS).unwrap();
+
+        assert_eq!(quote.token_amount, reserves.real_token_reserves);
+        assert_eq!(quote.sol_amount, exact_out.sol_amount);
+        assert!(quote.sol_amount < 1_000_000_000_000);
+        assert!(quote.completes_curve);
+        assert_eq!(quote.reserves_after.real_token_reserves, 0);
+
+        let partial = quote_buy(&reserves, 1_000_000_000, FEE_BPS).unwrap();
+        assert!(!partial.completes_curve);
+
+        // Asking for more than is left is clipped the same way
+        let more = quote_buy_exact_out(&reserves, u64::MAX, FEE_BPS).unwrap();
+        assert_eq!(more.token_amount, reserves.real_token_reserves);
+    }
+
+    #[test]
+    fn trades_never_shrink_the_invariant() {
+        let mut reserves = launch_reserves();
+
+        for sol_amount in [1_000, 1_000_000_007, 5_000_000_000, 17_000_000_001] {
+            let before = invariant(&reserves);
+            let buy = quote_buy(&reserves, sol_amount, FEE_BPS).unwrap();
+            assert!(invariant(&buy.reserves_after) >= before);
+            reserves = buy.reserves_after;
+
+            let before = invariant(&reserves);
+            let sell = quote_sell(&reserves, buy.token_amount / 3, FEE_BPS).unwrap();
+            assert!(invariant(&sell.reserves_after) >= before);
+            assert_eq!(sell.net_sol_amount + sell.fee_amount, sell.sol_amount);
+            reserves = sell.reserves_after;
+        }
+
+        // Selling more than the real SOL reserves can pay out is rejected
+        let everything = reserves.virtual_token_reserves;
+        assert!(quote_sell(&reserves, everything, FEE_BPS).is_err());
+    }
+}
\ No newline at end of file
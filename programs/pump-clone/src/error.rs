@@ -504,4 +504,77 @@ pub enum PumpCloneError {
     DiskSpaceInsufficient,
     
     #[msg("Network bandwidth exceeded")]
-    NetworkBandwidthExceeded,
\ No newline at end of file
+    NetworkBandwidthExceeded,
+    
+    #[msg("Wallet is not on the launch allowlist")]
+    WalletNotAllowlisted,
+    
+    #[msg("Block reason is too long (max 64 characters)")]
+    BlockReasonTooLong,
+    
+    #[msg("Presale is still active")]
+    PresaleActive,
+    
+    #[msg("Presale is not active")]
+    PresaleNotActive,
+    
+    #[msg("Presale allocation exceeded")]
+    PresaleAllocationExceeded,
+    
+    #[msg("Invalid presale parameters")]
+    InvalidPresaleParams,
+    
+    #[msg("Creator allocation exceeds the platform maximum")]
+    CreatorAllocationTooLarge,
+    
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    
+    #[msg("No vested tokens available to claim")]
+    NothingToClaim,
+    
+    #[msg("Signer has already approved this proposal")]
+    AlreadyApproved,
+    
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Governance tokens are locked until voting ends")]
+    GovernanceTokensLocked,
+    
+    #[msg("Proposal did not pass")]
+    ProposalDefeated,
+    
+    #[msg("Oracle confidence interval too wide")]
+    OracleConfidenceTooWide,
+    
+    #[msg("Bonding curve is neither migrated nor expired")]
+    CurveNotClosable,
+    
+    #[msg("Bonding curve deadline has passed")]
+    CurveDeadlinePassed,
+    
+    #[msg("Bonding curve deadline has not passed")]
+    CurveDeadlineNotReached,
+    
+    #[msg("Account is already at the current version")]
+    AccountAlreadyUpgraded,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Refund exceeds the tokens sold by the curve")]
+    RefundExceedsTokensSold,
+    
+    #[msg("Proposal was created for a previous multisig signer set")]
+    StaleAdminProposal,
+    
+    #[msg("Action cannot be executed by this authority")]
+    UnsupportedAdminAction,
+    
+    #[msg("Bonding curve has not completed")]
+    CurveNotComplete,
+    
+    #[msg("Comment content is empty or too long")]
+    InvalidCommentContent,
+}
\ No newline at end of file
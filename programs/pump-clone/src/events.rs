@@ -0,0 +1,442 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    curve::CurveReserves,
+    state::{AdminAction, ConfigUpdate, VersionedAccount},
+};
+
+/// Bumped whenever an event layout changes so indexers can decode old and
+/// new transactions side by side.
+pub const EVENT_VERSION: u8 = 1;
+
+/// Emits `$event` through Anchor's self-CPI so it is recorded in instruction
+/// data, which RPCs never truncate. Building with `log-events` falls back to
+/// `emit!` program logs. The instruction's accounts need `#[event_cpi]`.
+macro_rules! emit_event {
+    ($ctx:expr, $event:expr) => {{
+        let event = $event;
+        #[cfg(not(feature = "log-events"))]
+        {
+            let ctx = &$ctx;
+            anchor_lang::prelude::emit_cpi!(event);
+        }
+        #[cfg(feature = "log-events")]
+        {
+            let _ = &$ctx;
+            anchor_lang::prelude::emit!(event);
+        }
+    }};
+}
+
+pub(crate) use emit_event;
+
+/// Emitted for every curve trade. `sol_amount` is gross of fees and the
+/// reserves are snapshotted on both sides of the trade.
+#[event]
+pub struct TradeEvent {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub is_buy: bool,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub platform_fee: u64,
+    pub reserves_before: CurveReserves,
+    pub reserves_after: CurveReserves,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PresalePurchased {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub token_amount: u64,
+    pub sol_cost: u64,
+    pub fee_amount: u64,
+    pub allocation_remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PresaleFinalized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub tokens_sold: u64,
+    pub sol_raised: u64,
+    pub unclaimed_tokens: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KingOfTheHillChanged {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub previous_mint: Pubkey,
+    pub market_cap_usd: u64,
+    pub timestamp: i64,
+}
+
+/// Raised by the trade that takes the curve's USD market cap past the
+/// graduation market cap or buys out its real token reserves, or by
+/// `complete_bonding_curve`. `sol_usd_price` is zero when a buyout completed
+/// the curve while the oracle had no price.
+#[event]
+pub struct BondingCurveCompleted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub reserves: CurveReserves,
+    pub sol_usd_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondingCurveMigrated {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// Account the liquidity was withdrawn to for seeding the pool
+    pub pool: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub migration_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub bonding_curve: Pubkey,
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenClosed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub migrated: bool,
+    pub tokens_burned: u64,
+    pub dust_swept: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountUpgraded {
+    pub version: u8,
+    pub account: Pubkey,
+    pub kind: VersionedAccount,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingEscrowCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub vesting_escrow: Pubkey,
+    pub total_amount: u64,
+    pub cliff_timestamp: i64,
+    pub end_timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub twitter: String,
+    pub telegram: String,
+    pub website: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformInitialized {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub guardian: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub platform_fee_bps: u16,
+    pub graduation_market_cap_usd: u64,
+    pub oracle_feed: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub version: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub authorized_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub version: u8,
+    pub previous_flags: u8,
+    pub pause_flags: u8,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub version: u8,
+    pub previous_guardian: Pubkey,
+    pub guardian: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub version: u8,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeRecipientUpdated {
+    pub version: u8,
+    pub previous_fee_recipient: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletBlocked {
+    pub version: u8,
+    pub wallet: Pubkey,
+    pub authority: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletUnblocked {
+    pub version: u8,
+    pub wallet: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowlistCleared {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// The token creator, or the platform authority acting through an admin action
+    pub cleared_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminMultisigCreated {
+    pub version: u8,
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposalCreated {
+    pub version: u8,
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposalApproved {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposalExecuted {
+    pub version: u8,
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+    pub action: AdminAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub version: u8,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub update: ConfigUpdate,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub version: u8,
+    pub index: u64,
+    pub update: ConfigUpdate,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub version: u8,
+    pub index: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceInitialized {
+    pub version: u8,
+    pub governance: Pubkey,
+    pub governance_mint: Pubkey,
+    pub quorum_votes: u64,
+    pub proposal_threshold: u64,
+    pub voting_period: i64,
+    pub execution_delay: i64,
+}
+
+#[event]
+pub struct GovernanceAuthorityAccepted {
+    pub version: u8,
+    pub previous_authority: Pubkey,
+    pub governance: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesDeposited {
+    pub version: u8,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesWithdrawn {
+    pub version: u8,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalCreated {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalExecuted {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub action: AdminAction,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentPosted {
+    pub version: u8,
+    pub comment: Pubkey,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub reply_to: Option<Pubkey>,
+    pub content: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentLiked {
+    pub version: u8,
+    pub comment: Pubkey,
+    pub user: Pubkey,
+    pub likes: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentDeleted {
+    pub version: u8,
+    pub comment: Pubkey,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
\ No newline at end of file
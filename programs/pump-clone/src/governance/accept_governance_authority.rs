@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{GovernanceAuthorityAccepted, EVENT_VERSION},
+    state::GlobalState,
+};
+
+use super::state::Governance;
+
+/// The governance PDA can't sign `accept_authority`, so once the current
+/// authority has proposed it, anyone can complete the handover here.
+#[derive(Accounts)]
+pub struct AcceptGovernanceAuthority<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.pending_authority == Some(governance.key()) @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<AcceptGovernanceAuthority>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_authority = global_state.authority;
+
+    global_state.authority = ctx.accounts.governance.key();
+    global_state.pending_authority = None;
+
+    emit!(GovernanceAuthorityAccepted {
+        version: EVENT_VERSION,
+        previous_authority,
+        governance: global_state.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PumpCloneError;
+use crate::events::{VoteCast, EVENT_VERSION};
+
+use super::state::{GovernanceProposal, VoteRecord, VoterDeposit};
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_deposit", voter.key().as_ref()],
+        bump = voter_deposit.bump
+    )]
+    pub voter_deposit: Account<'info, VoterDeposit>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [b"vote_record", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CastVote>, support: bool) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    let deposit = &mut ctx.accounts.voter_deposit;
+
+    require!(
+        timestamp < proposal.voting_ends_at,
+        PumpCloneError::GovernanceVotingPeriodEnded
+    );
+    require!(
+        ctx.accounts.vote_record.weight == 0,
+        PumpCloneError::AlreadyVotedOnProposal
+    );
+
+    // Only deposits made before the proposal opened count, which snapshots
+    // voting power at proposal creation
+    require!(
+        deposit.amount > 0 && deposit.last_deposit_at < proposal.voting_starts_at,
+        PumpCloneError::InsufficientGovernanceTokens
+    );
+
+    let weight = deposit.amount;
+    if support {
+        proposal.votes_for = proposal.votes_for
+            .checked_add(weight)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    } else {
+        proposal.votes_against = proposal.votes_against
+            .checked_add(weight)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    }
+
+    deposit.locked_until = deposit.locked_until.max(proposal.voting_ends_at);
+
+    ctx.accounts.vote_record.set_inner(VoteRecord {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        support,
+        weight,
+        bump: ctx.bumps.vote_record,
+    });
+
+    emit!(VoteCast {
+        version: EVENT_VERSION,
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        support,
+        weight,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{GovernanceProposalCreated, EVENT_VERSION},
+    state::AdminAction,
+};
+
+use super::state::{Governance, GovernanceProposal, VoterDeposit};
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        seeds = [b"voter_deposit", proposer.key().as_ref()],
+        bump = voter_deposit.bump
+    )]
+    pub voter_deposit: Account<'info, VoterDeposit>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = GovernanceProposal::LEN,
+        seeds = [b"governance_proposal".as_ref(), &governance.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+    let governance = &mut ctx.accounts.governance;
+
+    require!(
+        ctx.accounts.voter_deposit.amount >= governance.proposal_threshold,
+        PumpCloneError::ProposalThresholdNotMet
+    );
+    // Signer rotation only applies to the admin multisig
+    require!(
+        !matches!(action, AdminAction::SetSigners { .. }),
+        PumpCloneError::UnsupportedAdminAction
+    );
+    action.validate()?;
+
+    let index = governance.proposal_count;
+    governance.proposal_count = governance.proposal_count
+        .checked_add(1)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    let voting_starts_at = Clock::get()?.unix_timestamp;
+    let voting_ends_at = voting_starts_at
+        .checked_add(governance.voting_period)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    let executable_at = voting_ends_at
+        .checked_add(governance.execution_delay)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    ctx.accounts.proposal.set_inner(GovernanceProposal {
+        index,
+        proposer: ctx.accounts.proposer.key(),
+        action: action.clone(),
+        votes_for: 0,
+        votes_against: 0,
+        voting_starts_at,
+        voting_ends_at,
+        executable_at,
+        executed: false,
+        bump: ctx.bumps.proposal,
+    });
+
+    emit!(GovernanceProposalCreated {
+        version: EVENT_VERSION,
+        proposal: ctx.accounts.proposal.key(),
+        index,
+        proposer: ctx.accounts.proposer.key(),
+        action,
+        voting_ends_at,
+        executable_at,
+    });
+
+    Ok(())
+}
\ No newline at end of file
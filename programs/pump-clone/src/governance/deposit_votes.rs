@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::error::PumpCloneError;
+use crate::events::{VotesDeposited, EVENT_VERSION};
+
+use super::state::{Governance, VoterDeposit};
+
+#[derive(Accounts)]
+pub struct DepositVotes<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump,
+        has_one = governance_mint
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = governance_mint,
+        associated_token::authority = governance
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = governance_mint,
+        associated_token::authority = voter
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoterDeposit::LEN,
+        seeds = [b"voter_deposit", voter.key().as_ref()],
+        bump
+    )]
+    pub voter_deposit: Account<'info, VoterDeposit>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositVotes>, amount: u64) -> Result<()> {
+    require!(amount > 0, PumpCloneError::InvalidAmount);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                to: ctx.accounts.governance_vault.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let deposit = &mut ctx.accounts.voter_deposit;
+    deposit.owner = ctx.accounts.voter.key();
+    deposit.amount = deposit.amount
+        .checked_add(amount)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    deposit.last_deposit_at = timestamp;
+    deposit.bump = ctx.bumps.voter_deposit;
+
+    emit!(VotesDeposited {
+        version: EVENT_VERSION,
+        voter: deposit.owner,
+        amount,
+        total_deposited: deposit.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
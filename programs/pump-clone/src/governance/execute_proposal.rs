@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{
+    admin::{self, AdminActionAccounts},
+    error::PumpCloneError,
+    events::{GovernanceProposalExecuted, EVENT_VERSION},
+    state::GlobalState,
+};
+
+use super::state::{Governance, GovernanceProposal};
+
+/// Permissionless once the proposal has passed and its execution delay has
+/// elapsed. The executor pays for any account the action creates.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ PumpCloneError::ProposalAlreadyExecuted
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// CHECK: only used as the destination of a `WithdrawFees` or
+    /// `Withdraw` action and checked against the recipient it pays
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: the account the action operates on (blocklist PDA, bonding
+    /// curve, pending config change or account to upgrade); validated
+    /// against the action by the executor
+    #[account(mut)]
+    pub target: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: only read by a `CompleteBondingCurve` action and checked
+    /// against `global_state.oracle_feed`
+    pub oracle_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the curve's token account a `Withdraw` action empties; checked
+    /// to be the curve's associated token account
+    #[account(mut)]
+    pub bonding_curve_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the recipient's token account a `Withdraw` action pays the
+    /// curve's tokens into; checked to be its associated token account
+    #[account(mut)]
+    pub recipient_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        timestamp >= proposal.executable_at,
+        PumpCloneError::TimelockPeriodNotElapsed
+    );
+    require!(
+        proposal.total_votes() >= ctx.accounts.governance.quorum_votes,
+        PumpCloneError::QuorumNotReached
+    );
+    require!(
+        proposal.votes_for > proposal.votes_against,
+        PumpCloneError::ProposalDefeated
+    );
+
+    let action = proposal.action.clone();
+    let accounts = AdminActionAccounts {
+        authority: ctx.accounts.governance.key(),
+        payer: ctx.accounts.executor.to_account_info(),
+        fee_vault: ctx.accounts.fee_vault.to_account_info(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        recipient: ctx.accounts.recipient.to_account_info(),
+        target: ctx.accounts.target.as_ref().map(|target| target.to_account_info()),
+        oracle_feed: ctx.accounts.oracle_feed.as_ref().map(|feed| feed.to_account_info()),
+        bonding_curve_token_account: ctx
+            .accounts
+            .bonding_curve_token_account
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        recipient_token_account: ctx
+            .accounts
+            .recipient_token_account
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        token_program: ctx.accounts.token_program.as_ref().map(|program| program.to_account_info()),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+
+    admin::execute(
+        &mut ctx.accounts.global_state,
+        &action,
+        &accounts,
+        proposal.voting_starts_at,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.executed = true;
+
+    emit!(GovernanceProposalExecuted {
+        version: EVENT_VERSION,
+        proposal: proposal.key(),
+        index: proposal.index,
+        action,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
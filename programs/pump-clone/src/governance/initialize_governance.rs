@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::PumpCloneError,
+    events::{GovernanceInitialized, EVENT_VERSION},
+    state::GlobalState,
+};
+
+use super::state::Governance;
+
+/// Sets up holder governance. Proposals can only be executed once the
+/// platform authority has been handed to the governance PDA through
+/// `propose_authority` and `accept_governance_authority`.
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Governance::LEN,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = governance_mint,
+        associated_token::authority = governance
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GovernanceParams {
+    pub quorum_votes: u64,
+    pub proposal_threshold: u64,
+    pub voting_period: i64,
+    pub execution_delay: i64,
+}
+
+pub fn handler(ctx: Context<InitializeGovernance>, params: GovernanceParams) -> Result<()> {
+    require!(
+        params.quorum_votes > 0
+            && params.proposal_threshold > 0
+            && params.voting_period >= Governance::MIN_VOTING_PERIOD
+            && params.voting_period <= Governance::MAX_VOTING_PERIOD
+            && params.execution_delay >= 0,
+        PumpCloneError::InvalidGovernanceParams
+    );
+
+    ctx.accounts.governance.set_inner(Governance {
+        governance_mint: ctx.accounts.governance_mint.key(),
+        quorum_votes: params.quorum_votes,
+        proposal_threshold: params.proposal_threshold,
+        voting_period: params.voting_period,
+        execution_delay: params.execution_delay,
+        proposal_count: 0,
+        bump: ctx.bumps.governance,
+    });
+
+    emit!(GovernanceInitialized {
+        version: EVENT_VERSION,
+        governance: ctx.accounts.governance.key(),
+        governance_mint: ctx.accounts.governance_mint.key(),
+        quorum_votes: params.quorum_votes,
+        proposal_threshold: params.proposal_threshold,
+        voting_period: params.voting_period,
+        execution_delay: params.execution_delay,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+// Every instruction module exports a `handler`; the #[program] module calls
+// them by path, and the globs only exist to re-export the account structs.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod state;
+pub mod initialize_governance;
+pub mod accept_governance_authority;
+pub mod deposit_votes;
+pub mod withdraw_votes;
+pub mod create_proposal;
+pub mod cast_vote;
+pub mod execute_proposal;
+
+pub use state::*;
+pub use initialize_governance::*;
+pub use accept_governance_authority::*;
+pub use deposit_votes::*;
+pub use withdraw_votes::*;
+pub use create_proposal::*;
+pub use cast_vote::*;
+pub use execute_proposal::*;
\ No newline at end of file
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AdminAction;
+
+#[account]
+pub struct Governance {
+    pub governance_mint: Pubkey,
+    pub quorum_votes: u64,
+    pub proposal_threshold: u64,
+    pub voting_period: i64,
+    pub execution_delay: i64,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl Governance {
+    pub const MIN_VOTING_PERIOD: i64 = 24 * 60 * 60;
+    pub const MAX_VOTING_PERIOD: i64 = 14 * 24 * 60 * 60;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // governance_mint
+        8 + // quorum_votes
+        8 + // proposal_threshold
+        8 + // voting_period
+        8 + // execution_delay
+        8 + // proposal_count
+        1; // bump
+}
+
+/// Governance tokens escrowed by a voter. Voting weight is the deposited
+/// amount, and a deposit stays locked until every proposal it voted on has
+/// closed, so the same tokens can't be moved to another wallet and counted
+/// twice.
+#[account]
+pub struct VoterDeposit {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub last_deposit_at: i64,
+    pub locked_until: i64,
+    pub bump: u8,
+}
+
+impl VoterDeposit {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // amount
+        8 + // last_deposit_at
+        8 + // locked_until
+        1; // bump
+}
+
+#[account]
+pub struct GovernanceProposal {
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub voting_starts_at: i64,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl GovernanceProposal {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // index
+        32 + // proposer
+        AdminAction::LEN + // action
+        8 + // votes_for
+        8 + // votes_against
+        8 + // voting_starts_at
+        8 + // voting_ends_at
+        8 + // executable_at
+        1 + // executed
+        1; // bump
+
+    pub fn total_votes(&self) -> u64 {
+        self.votes_for.saturating_add(self.votes_against)
+    }
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // voter
+        1 + // support
+        8 + // weight
+        1; // bump
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::error::PumpCloneError;
+use crate::events::{VotesWithdrawn, EVENT_VERSION};
+
+use super::state::{Governance, VoterDeposit};
+
+#[derive(Accounts)]
+pub struct WithdrawVotes<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump,
+        has_one = governance_mint
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = governance_mint,
+        associated_token::authority = governance
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = governance_mint,
+        associated_token::authority = voter
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_deposit", voter.key().as_ref()],
+        bump = voter_deposit.bump,
+        constraint = voter_deposit.owner == voter.key() @ PumpCloneError::AccessControlViolation
+    )]
+    pub voter_deposit: Account<'info, VoterDeposit>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawVotes>, amount: u64) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let deposit = &ctx.accounts.voter_deposit;
+
+    require!(amount > 0, PumpCloneError::InvalidAmount);
+    require!(amount <= deposit.amount, PumpCloneError::InsufficientGovernanceTokens);
+    require!(
+        timestamp >= deposit.locked_until,
+        PumpCloneError::GovernanceTokensLocked
+    );
+
+    let seeds = &[b"governance".as_ref(), &[ctx.accounts.governance.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.governance_vault.to_account_info(),
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ctx.accounts.governance.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    let deposit = &mut ctx.accounts.voter_deposit;
+    deposit.amount -= amount;
+
+    emit!(VotesWithdrawn {
+        version: EVENT_VERSION,
+        voter: deposit.owner,
+        amount,
+        total_deposited: deposit.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{AuthorityTransferred, EVENT_VERSION},
+    state::GlobalState,
+};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.pending_authority == Some(new_authority.key()) @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_authority = global_state.authority;
+
+    global_state.authority = ctx.accounts.new_authority.key();
+    global_state.pending_authority = None;
+
+    emit!(AuthorityTransferred {
+        version: EVENT_VERSION,
+        previous_authority,
+        authority: global_state.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
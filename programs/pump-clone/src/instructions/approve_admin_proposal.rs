@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{AdminProposalApproved, EVENT_VERSION},
+    state::{AdminMultisig, AdminProposal},
+};
+
+#[derive(Accounts)]
+pub struct ApproveAdminProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin_multisig"],
+        bump = admin_multisig.bump
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"admin_proposal",
+            admin_multisig.key().as_ref(),
+            &admin_proposal.index.to_le_bytes()
+        ],
+        bump = admin_proposal.bump,
+        constraint = !admin_proposal.executed @ PumpCloneError::ProposalAlreadyExecuted,
+        constraint = admin_proposal.signers_version == admin_multisig.signers_version @ PumpCloneError::StaleAdminProposal
+    )]
+    pub admin_proposal: Account<'info, AdminProposal>,
+}
+
+pub fn handler(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+    let signer_index = ctx.accounts.admin_multisig
+        .signer_index(&ctx.accounts.signer.key())
+        .ok_or(PumpCloneError::UnauthorizedAdmin)?;
+
+    let proposal = &mut ctx.accounts.admin_proposal;
+    require!(!proposal.has_approved(signer_index), PumpCloneError::AlreadyApproved);
+
+    proposal.approve(signer_index);
+
+    emit!(AdminProposalApproved {
+        version: EVENT_VERSION,
+        proposal: proposal.key(),
+        signer: ctx.accounts.signer.key(),
+        approvals: proposal.approval_count(),
+        threshold: ctx.accounts.admin_multisig.threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{WalletBlocked, EVENT_VERSION},
+    state::{BlockedWallet, GlobalState},
+};
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockWallet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = BlockedWallet::LEN,
+        seeds = [b"blocked_wallet", wallet.as_ref()],
+        bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BlockWallet>, wallet: Pubkey, reason: String) -> Result<()> {
+    require!(
+        reason.len() <= BlockedWallet::MAX_REASON_LEN,
+        PumpCloneError::BlockReasonTooLong
+    );
+
+    let blocked_at = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.blocked_wallet.set_inner(BlockedWallet {
+        wallet,
+        blocked_by: ctx.accounts.authority.key(),
+        reason: reason.clone(),
+        blocked_at,
+        bump: ctx.bumps.blocked_wallet,
+    });
+
+    emit!(WalletBlocked {
+        version: EVENT_VERSION,
+        wallet,
+        authority: ctx.accounts.authority.key(),
+        reason,
+        timestamp: blocked_at,
+    });
+
+    Ok(())
+}
\ No newline at end of file
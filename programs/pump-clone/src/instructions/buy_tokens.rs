@@ -1,220 +1,273 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use crate::state::*;
-use crate::errors::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
 
+use crate::{
+    constants::BONDING_CURVE_SEED,
+    curve::{self, CurveReserves},
+    error::PumpCloneError,
+    events::{emit_event, BondingCurveCompleted, TradeEvent, EVENT_VERSION},
+    oracle::sol_usd_price,
+    state::{BondingCurve, GlobalState, KingOfTheHill, Position, PriceHistory, TokenMetadata, UserStats},
+    utils::{allowlist_leaf, record_price, require_not_blocked, track_market_cap, verify_merkle_proof},
+};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct BuyTokens<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
-    
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused(GlobalState::PAUSE_BUY) @ PumpCloneError::EmergencyPauseActivated
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
-        seeds = [b"bonding_curve", token_mint.key().as_ref()],
-        bump = bonding_curve.bump,
-        has_one = token_mint,
-        constraint = bonding_curve.is_active @ PumpError::BondingCurveInactive
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = mint
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
-    
-    #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
-    
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
     #[account(
         mut,
-        associated_token::mint = token_mint,
+        associated_token::mint = mint,
         associated_token::authority = bonding_curve
     )]
     pub bonding_curve_token_account: Account<'info, TokenAccount>,
-    
+
     #[account(
         init_if_needed,
         payer = buyer,
-        associated_token::mint = token_mint,
+        associated_token::mint = mint,
         associated_token::authority = buyer
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
-    
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata", mint.key().as_ref()],
+        bump = token_metadata.bump
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = KingOfTheHill::LEN,
+        seeds = [b"king_of_the_hill"],
+        bump
+    )]
+    pub king_of_the_hill: Account<'info, KingOfTheHill>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = UserStats::LEN,
+        seeds = [b"user_stats", buyer.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = Position::LEN,
+        seeds = [b"position", buyer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [b"price_history", mint.key().as_ref()],
+        bump = price_history.load()?.bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+
+    /// CHECK: validated against `global_state.oracle_feed` and read through
+    /// the `Oracle` trait
+    #[account(address = global_state.oracle_feed @ PumpCloneError::InvalidPriceFeed)]
+    pub oracle_feed: UncheckedAccount<'info>,
+
+    /// CHECK: blocklist PDA for the buyer; trading is rejected if it has been initialized
+    #[account(
+        seeds = [b"blocked_wallet", buyer.key().as_ref()],
+        bump
+    )]
+    pub blocked_wallet: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"fee_vault"],
         bump
     )]
     pub fee_vault: SystemAccount<'info>,
-    
+
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn buy_tokens(ctx: Context<BuyTokens>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    let buyer = &ctx.accounts.buyer;
-    let token_mint = &ctx.accounts.token_mint;
-    
-    require!(sol_amount > 0, PumpError::InvalidAmount);
-    require!(sol_amount >= bonding_curve.min_buy_amount, PumpError::BelowMinimumBuy);
-    require!(sol_amount <= bonding_curve.max_buy_amount, PumpError::AboveMaximumBuy);
-    
-    // Calculate tokens to mint based on bonding curve
-    let tokens_to_mint = calculate_tokens_for_sol(
+/// Spends up to `sol_amount` lamports, fees included. A buy larger than the
+/// remaining real reserves is clipped to them and charged only their cost.
+pub fn handler(
+    ctx: Context<BuyTokens>,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require_not_blocked(&ctx.accounts.blocked_wallet)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let buyer = ctx.accounts.buyer.key();
+    let mint = ctx.accounts.mint.key();
+
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+
+    if let Some(root) = bonding_curve.allowlist_root() {
+        require!(
+            verify_merkle_proof(&allowlist_proof, root, allowlist_leaf(&buyer)),
+            PumpCloneError::WalletNotAllowlisted
+        );
+    }
+
+    require!(!bonding_curve.is_complete(), PumpCloneError::TokenAlreadyGraduated);
+    require!(!bonding_curve.is_presale_pending(), PumpCloneError::PresaleActive);
+    require!(
+        !bonding_curve.is_past_deadline(timestamp),
+        PumpCloneError::CurveDeadlinePassed
+    );
+
+    let reserves_before = CurveReserves::from(&*bonding_curve);
+    let quote = curve::quote_buy(
+        &reserves_before,
         sol_amount,
-        bonding_curve.virtual_sol_reserves,
-        bonding_curve.virtual_token_reserves,
-        bonding_curve.real_sol_reserves,
-        bonding_curve.real_token_reserves
+        ctx.accounts.global_state.platform_fee_bps,
     )?;
-    
-    require!(tokens_to_mint >= min_tokens_out, PumpError::SlippageExceeded);
-    require!(tokens_to_mint > 0, PumpError::InvalidTokenAmount);
-    
-    // Check if purchase would exceed graduation threshold
-    let new_real_sol_reserves = bonding_curve.real_sol_reserves
-        .checked_add(sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    if new_real_sol_reserves >= bonding_curve.graduation_threshold {
-        return Err(PumpError::GraduationThresholdReached.into());
-    }
-    
-    // Calculate fees
-    let fee_amount = sol_amount
-        .checked_mul(bonding_curve.fee_basis_points as u64)
-        .ok_or(PumpError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let net_sol_amount = sol_amount
-        .checked_sub(fee_amount)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    // Transfer SOL from buyer to bonding curve
-    let transfer_instruction = anchor_lang::system_program::Transfer {
-        from: buyer.to_account_info(),
-        to: bonding_curve.to_account_info(),
-    };
-    
+    require!(
+        quote.token_amount >= min_tokens_out,
+        PumpCloneError::SlippageToleranceExceeded
+    );
+
+    // The curve receives SOL and signs the token transfer below, so its data
+    // can't stay borrowed across the CPIs
+    let curve_bump = bonding_curve.bump;
+    drop(bonding_curve);
+
+    let net_sol_amount = quote.sol_amount - quote.fee_amount;
+
     anchor_lang::system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
-            transfer_instruction,
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.bonding_curve.to_account_info(),
+            },
         ),
         net_sol_amount,
     )?;
-    
-    // Transfer fee to fee vault
-    if fee_amount > 0 {
-        let fee_transfer_instruction = anchor_lang::system_program::Transfer {
-            from: buyer.to_account_info(),
-            to: ctx.accounts.fee_vault.to_account_info(),
-        };
-        
+
+    if quote.fee_amount > 0 {
         anchor_lang::system_program::transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
-                fee_transfer_instruction,
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
             ),
-            fee_amount,
+            quote.fee_amount,
         )?;
     }
-    
-    // Mint tokens to buyer
-    let seeds = &[
-        b"bonding_curve",
-        token_mint.key().as_ref(),
-        &[bonding_curve.bump],
-    ];
+
+    let seeds = &[BONDING_CURVE_SEED, mint.as_ref(), &[curve_bump]];
     let signer_seeds = &[&seeds[..]];
-    
-    let mint_to_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        token::MintTo {
-            mint: token_mint.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: bonding_curve.to_account_info(),
-        },
-        signer_seeds,
-    );
-    
-    token::mint_to(mint_to_ctx, tokens_to_mint)?;
-    
-    // Update bonding curve state
-    bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
-        .checked_add(net_sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
-        .checked_sub(tokens_to_mint)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    bonding_curve.virtual_sol_reserves = bonding_curve.virtual_sol_reserves
-        .checked_add(net_sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    bonding_curve.virtual_token_reserves = bonding_curve.virtual_token_reserves
-        .checked_sub(tokens_to_mint)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    bonding_curve.total_supply = bonding_curve.total_supply
-        .checked_add(tokens_to_mint)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    bonding_curve.last_trade_timestamp = Clock::get()?.unix_timestamp;
-    
-    // Emit trade event
-    emit!(TradeEvent {
-        trader: buyer.key(),
-        token_mint: token_mint.key(),
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        quote.token_amount,
+    )?;
+
+    // The price only drives graduation and king of the hill, so a stale or
+    // unreadable feed defers those rather than failing the trade
+    let sol_usd_price = sol_usd_price(&ctx.accounts.oracle_feed, &ctx.accounts.global_state).ok();
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.accumulate_price(timestamp)?;
+    bonding_curve.set_reserves(&quote.reserves_after);
+    bonding_curve.record_trade()?;
+
+    let mut events = track_market_cap(
+        &mut bonding_curve,
+        &mut ctx.accounts.token_metadata,
+        &mut ctx.accounts.king_of_the_hill,
+        ctx.bumps.king_of_the_hill,
+        &ctx.accounts.global_state,
+        sol_usd_price,
+        timestamp,
+    )?;
+
+    // Buying out the real token reserves completes the curve whatever its
+    // USD market cap
+    if quote.completes_curve && !bonding_curve.is_complete() {
+        bonding_curve.set_complete();
+        events.completed = Some(BondingCurveCompleted {
+            version: EVENT_VERSION,
+            mint,
+            reserves: quote.reserves_after,
+            sol_usd_price: sol_usd_price.unwrap_or_default(),
+            timestamp,
+        });
+    }
+
+    record_price(&ctx.accounts.price_history, &bonding_curve, quote.sol_amount, timestamp)?;
+    drop(bonding_curve);
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.ensure_initialized(buyer, ctx.bumps.user_stats);
+    user_stats.update_buy_stats(quote.token_amount, quote.sol_amount, quote.fee_amount, timestamp)?;
+
+    let position = &mut ctx.accounts.position;
+    position.ensure_initialized(buyer, mint, ctx.bumps.position, timestamp);
+    position.record_buy(quote.token_amount, quote.sol_amount, timestamp)?;
+
+    emit_event!(ctx, TradeEvent {
+        version: EVENT_VERSION,
+        mint,
+        user: buyer,
         is_buy: true,
-        sol_amount: net_sol_amount,
-        token_amount: tokens_to_mint,
-        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
-        virtual_token_reserves: bonding_curve.virtual_token_reserves,
-        real_sol_reserves: bonding_curve.real_sol_reserves,
-        real_token_reserves: bonding_curve.real_token_reserves,
-        timestamp: bonding_curve.last_trade_timestamp,
+        sol_amount: quote.sol_amount,
+        token_amount: quote.token_amount,
+        platform_fee: quote.fee_amount,
+        reserves_before,
+        reserves_after: quote.reserves_after,
+        slot: Clock::get()?.slot,
+        timestamp,
     });
-    
-    Ok(())
-}
 
-fn calculate_tokens_for_sol(
-    sol_amount: u64,
-    virtual_sol_reserves: u64,
-    virtual_token_reserves: u64,
-    _real_sol_reserves: u64,
-    _real_token_reserves: u64,
-) -> Result<u64> {
-    // Using constant product formula: x * y = k
-    // tokens_out = (token_reserves * sol_in) / (sol_reserves + sol_in)
-    
-    let numerator = (virtual_token_reserves as u128)
-        .checked_mul(sol_amount as u128)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let denominator = (virtual_sol_reserves as u128)
-        .checked_add(sol_amount as u128)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let tokens_out = numerator
-        .checked_div(denominator)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    require!(tokens_out <= u64::MAX as u128, PumpError::MathOverflow);
-    
-    Ok(tokens_out as u64)
-}
+    if let Some(event) = events.king_changed {
+        emit_event!(ctx, event);
+    }
+    if let Some(event) = events.completed {
+        emit_event!(ctx, event);
+    }
 
-#[event]
-pub struct TradeEvent {
-    pub trader: Pubkey,
-    pub token_mint: Pubkey,
-    pub is_buy: bool,
-    pub sol_amount: u64,
-    pub token_amount: u64,
-    pub virtual_sol_reserves: u64,
-    pub virtual_token_reserves: u64,
-    pub real_sol_reserves: u64,
-    pub real_token_reserves: u64,
-    pub timestamp: i64,
+    Ok(())
 }
\ No newline at end of file
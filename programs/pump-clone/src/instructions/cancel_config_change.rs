@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{ConfigChangeCancelled, EVENT_VERSION},
+    state::{GlobalState, PendingConfigChange},
+};
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"config_change", &pending_config_change.index.to_le_bytes()],
+        bump = pending_config_change.bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+}
+
+pub fn handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    emit!(ConfigChangeCancelled {
+        version: EVENT_VERSION,
+        index: ctx.accounts.pending_config_change.index,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::PumpCloneError,
+    events::{VestedTokensClaimed, EVENT_VERSION},
+    state::VestingEscrow,
+};
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting_escrow", mint.key().as_ref()],
+        bump = vesting_escrow.bump,
+        has_one = mint,
+        has_one = beneficiary @ PumpCloneError::UnauthorizedCreator
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_escrow
+    )]
+    pub vesting_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimVested>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let claimable = ctx.accounts.vesting_escrow.claimable_amount(timestamp)?;
+
+    require!(claimable > 0, PumpCloneError::NothingToClaim);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        b"vesting_escrow",
+        mint_key.as_ref(),
+        &[ctx.accounts.vesting_escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vesting_token_account.to_account_info(),
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: ctx.accounts.vesting_escrow.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        claimable,
+    )?;
+
+    let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+    vesting_escrow.claimed_amount = vesting_escrow.claimed_amount
+        .checked_add(claimable)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    emit!(VestedTokensClaimed {
+        version: EVENT_VERSION,
+        mint: mint_key,
+        beneficiary: vesting_escrow.beneficiary,
+        amount: claimable,
+        total_claimed: vesting_escrow.claimed_amount,
+        remaining: vesting_escrow.total_amount - vesting_escrow.claimed_amount,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
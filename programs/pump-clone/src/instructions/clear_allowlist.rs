@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, events::{AllowlistCleared, EVENT_VERSION}, state::BondingCurve};
+
+/// Opens a private launch to public trading. This is one-way: a creator
+/// cannot put an allowlist back on a curve that is already public.
+#[derive(Accounts)]
+pub struct ClearAllowlist<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ PumpCloneError::UnauthorizedCreator
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+}
+
+pub fn handler(ctx: Context<ClearAllowlist>) -> Result<()> {
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.allowlist_root = [0; 32];
+
+    emit!(AllowlistCleared {
+        version: EVENT_VERSION,
+        mint: bonding_curve.mint,
+        cleared_by: ctx.accounts.creator.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Burn, CloseAccount, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::PumpCloneError,
+    events::{TokenClosed, EVENT_VERSION},
+    state::{BondingCurve, GlobalState, PriceHistory, TokenMetadata},
+};
+
+/// Permissionless: anyone can reclaim a dead launch. Rent and any SOL still
+/// tracked in the real reserves go back to the creator; untracked SOL left on
+/// the curve goes to the fee vault.
+#[derive(Accounts)]
+pub struct CloseToken<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: rent destination, checked against the curve's creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = mint,
+        has_one = creator @ PumpCloneError::UnauthorizedCreator,
+        close = creator
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata", mint.key().as_ref()],
+        bump = token_metadata.bump,
+        close = creator
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        mut,
+        seeds = [b"price_history", mint.key().as_ref()],
+        bump = price_history.load()?.bump,
+        close = creator
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseToken>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+
+    require!(
+        bonding_curve.is_closable(ctx.accounts.global_state.token_expiry, timestamp),
+        PumpCloneError::CurveNotClosable
+    );
+
+    let migrated = bonding_curve.is_complete();
+    let real_sol_reserves = bonding_curve.real_sol_reserves;
+    let curve_bump = bonding_curve.bump;
+    drop(bonding_curve);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[b"bonding_curve", mint_key.as_ref(), &[curve_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    // Burn whatever the curve still holds so the token account can close
+    let tokens_burned = ctx.accounts.bonding_curve_token_account.amount;
+    if tokens_burned > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            tokens_burned,
+        )?;
+    }
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    // Sweep dust above the curve's rent and tracked reserves to the fee
+    // vault; both are returned to the creator when the account closes
+    let curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(curve_info.data_len());
+    let dust = curve_info
+        .lamports()
+        .saturating_sub(rent_exempt)
+        .saturating_sub(real_sol_reserves);
+    if dust > 0 {
+        **curve_info.try_borrow_mut_lamports()? -= dust;
+        **ctx.accounts.fee_vault.to_account_info().try_borrow_mut_lamports()? += dust;
+    }
+
+    emit!(TokenClosed {
+        version: EVENT_VERSION,
+        mint: mint_key,
+        creator: ctx.accounts.creator.key(),
+        migrated,
+        tokens_burned,
+        dust_swept: dust,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::BONDING_CURVE_SEED,
+    curve::CurveReserves,
+    error::PumpCloneError,
+    events::{emit_event, BondingCurveCompleted, EVENT_VERSION},
+    oracle::{lamports_to_usd, sol_usd_price},
+    state::{BondingCurve, GlobalState},
+};
+
+/// Completes a curve whose USD market cap has reached the graduation market
+/// cap without a trade crossing it, e.g. after the SOL price moved.
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CompleteBondingCurve<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin,
+        constraint = !global_state.is_paused(GlobalState::PAUSE_MIGRATE) @ PumpCloneError::EmergencyPauseActivated
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    /// CHECK: validated against `global_state.oracle_feed` and read through
+    /// the `Oracle` trait
+    #[account(address = global_state.oracle_feed @ PumpCloneError::InvalidPriceFeed)]
+    pub oracle_feed: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CompleteBondingCurve>) -> Result<()> {
+    let sol_usd_price = sol_usd_price(&ctx.accounts.oracle_feed, &ctx.accounts.global_state)?;
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    let event = complete(&mut bonding_curve, &ctx.accounts.global_state, sol_usd_price)?;
+    drop(bonding_curve);
+
+    emit_event!(ctx, event);
+
+    Ok(())
+}
+
+/// Marks `bonding_curve` complete if its market cap at `sol_usd_price` has
+/// reached the graduation market cap. Shared with the admin executor.
+pub fn complete(
+    bonding_curve: &mut BondingCurve,
+    global_state: &GlobalState,
+    sol_usd_price: u64,
+) -> Result<BondingCurveCompleted> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(!bonding_curve.is_complete(), PumpCloneError::TokenAlreadyGraduated);
+    bonding_curve.require_not_refunding(timestamp)?;
+
+    let market_cap_usd = lamports_to_usd(bonding_curve.market_cap()?, sol_usd_price)?;
+    require!(
+        market_cap_usd >= global_state.graduation_market_cap_usd,
+        PumpCloneError::GraduationMarketCapNotReached
+    );
+
+    bonding_curve.set_complete();
+
+    Ok(BondingCurveCompleted {
+        version: EVENT_VERSION,
+        mint: bonding_curve.mint,
+        reserves: CurveReserves::from(&*bonding_curve),
+        sol_usd_price,
+        timestamp,
+    })
+}
\ No newline at end of file
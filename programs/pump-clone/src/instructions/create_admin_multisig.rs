@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{AdminMultisigCreated, EVENT_VERSION},
+    state::{AdminMultisig, GlobalState},
+};
+
+/// Creates the M-of-N admin multisig. It only gains control once the current
+/// authority proposes the multisig PDA and an `AcceptAuthority` proposal is
+/// executed through it.
+#[derive(Accounts)]
+pub struct CreateAdminMultisig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminMultisig::LEN,
+        seeds = [b"admin_multisig"],
+        bump
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateAdminMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    AdminMultisig::validate_signers(&signers, threshold)?;
+
+    ctx.accounts.admin_multisig.set_inner(AdminMultisig {
+        signers: signers.clone(),
+        threshold,
+        proposal_count: 0,
+        signers_version: 0,
+        bump: ctx.bumps.admin_multisig,
+    });
+
+    emit!(AdminMultisigCreated {
+        version: EVENT_VERSION,
+        multisig: ctx.accounts.admin_multisig.key(),
+        signers,
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
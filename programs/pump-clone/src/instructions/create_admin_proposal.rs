@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{AdminProposalCreated, EVENT_VERSION},
+    state::{AdminAction, AdminMultisig, AdminProposal},
+};
+
+#[derive(Accounts)]
+pub struct CreateAdminProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_multisig"],
+        bump = admin_multisig.bump
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = AdminProposal::LEN,
+        seeds = [
+            b"admin_proposal",
+            admin_multisig.key().as_ref(),
+            &admin_multisig.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub admin_proposal: Account<'info, AdminProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateAdminProposal>, action: AdminAction) -> Result<()> {
+    let signer_index = ctx.accounts.admin_multisig
+        .signer_index(&ctx.accounts.proposer.key())
+        .ok_or(PumpCloneError::UnauthorizedAdmin)?;
+
+    action.validate()?;
+
+    let multisig = &mut ctx.accounts.admin_multisig;
+    let index = multisig.proposal_count;
+    multisig.proposal_count = multisig.proposal_count
+        .checked_add(1)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    let created_at = Clock::get()?.unix_timestamp;
+    let mut proposal = AdminProposal {
+        multisig: multisig.key(),
+        index,
+        proposer: ctx.accounts.proposer.key(),
+        action,
+        approvals: 0,
+        executed: false,
+        created_at,
+        signers_version: multisig.signers_version,
+        bump: ctx.bumps.admin_proposal,
+    };
+
+    // Proposing counts as the proposer's approval
+    proposal.approve(signer_index);
+    ctx.accounts.admin_proposal.set_inner(proposal);
+
+    emit!(AdminProposalCreated {
+        version: EVENT_VERSION,
+        multisig: ctx.accounts.admin_multisig.key(),
+        proposal: ctx.accounts.admin_proposal.key(),
+        index,
+        proposer: ctx.accounts.proposer.key(),
+        timestamp: created_at,
+    });
+
+    Ok(())
+}
\ No newline at end of file
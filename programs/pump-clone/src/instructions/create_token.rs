@@ -10,11 +10,13 @@ use anchor_spl::{
 
 use crate::{
     constants::*,
-    errors::PumpError,
-    state::{BondingCurve, GlobalConfig},
-    utils::*,
+    error::PumpCloneError,
+    events::{emit_event, TokenCreated, VestingEscrowCreated, EVENT_VERSION},
+    state::{BondingCurve, GlobalState, PriceHistory, TokenMetadata, UserStats, VestingEscrow},
+    utils::require_not_blocked,
 };
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 #[instruction(params: CreateTokenParams)]
 pub struct CreateToken<'info> {
@@ -22,11 +24,11 @@ pub struct CreateToken<'info> {
     pub creator: Signer<'info>,
 
     #[account(
-        seeds = [GLOBAL_CONFIG_SEED],
-        bump,
-        has_one = fee_recipient
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused(GlobalState::PAUSE_CREATE) @ PumpCloneError::EmergencyPauseActivated
     )]
-    pub global_config: Account<'info, GlobalConfig>,
+    pub global_state: Account<'info, GlobalState>,
 
     #[account(
         init,
@@ -44,7 +46,7 @@ pub struct CreateToken<'info> {
         seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
         bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
 
     #[account(
         init,
@@ -54,13 +56,62 @@ pub struct CreateToken<'info> {
     )]
     pub bonding_curve_token_account: Account<'info, TokenAccount>,
 
-    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(
+        init,
+        payer = creator,
+        space = TokenMetadata::LEN,
+        seeds = [b"token_metadata", mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PriceHistory::LEN,
+        seeds = [b"price_history", mint.key().as_ref()],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = UserStats::LEN,
+        seeds = [b"user_stats", creator.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Only required when `params.creator_allocation` is set
+    #[account(
+        init,
+        payer = creator,
+        space = VestingEscrow::LEN,
+        seeds = [b"vesting_escrow", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_escrow
+    )]
+    pub vesting_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metaplex metadata PDA for `mint`, created and checked by the
+    /// token metadata program
     #[account(mut)]
     pub metadata: UncheckedAccount<'info>,
 
-    /// CHECK: fee recipient from global config
-    #[account(mut)]
-    pub fee_recipient: UncheckedAccount<'info>,
+    /// CHECK: blocklist PDA for the creator; creation is rejected if it has been initialized
+    #[account(
+        seeds = [b"blocked_wallet", creator.key().as_ref()],
+        bump
+    )]
+    pub blocked_wallet: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -74,65 +125,147 @@ pub struct CreateTokenParams {
     pub name: String,
     pub symbol: String,
     pub uri: String,
-    pub initial_virtual_token_reserves: u64,
-    pub initial_virtual_sol_reserves: u64,
-    pub initial_real_token_reserves: u64,
+    pub description: String,
+    pub image_uri: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub website: String,
+    pub nsfw: bool,
+    pub show_name: bool,
+    /// Merkle root of wallets allowed to buy during a private presale
+    pub allowlist_root: Option<[u8; 32]>,
+    pub presale: Option<PresaleParams>,
+    pub creator_allocation: Option<CreatorAllocationParams>,
+    /// Seconds after launch by which the curve must graduate; past it,
+    /// holders can only refund
+    pub deadline_duration: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PresaleParams {
+    /// Merkle root over keccak256(wallet || allocation) leaves
+    pub merkle_root: [u8; 32],
+    /// Sum of all allocations in the tree, in token base units
+    pub allocation: u64,
+    pub duration: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreatorAllocationParams {
+    /// Share of `TOTAL_SUPPLY` reserved for the creator
+    pub bps: u16,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
 }
 
 impl<'info> CreateToken<'info> {
     pub fn validate(&self, params: &CreateTokenParams) -> Result<()> {
+        require_not_blocked(&self.blocked_wallet)?;
+
         // Validate token name
         require!(
             !params.name.is_empty() && params.name.len() <= MAX_NAME_LENGTH,
-            PumpError::InvalidTokenName
+            PumpCloneError::TokenNameTooLong
         );
 
         // Validate token symbol
         require!(
             !params.symbol.is_empty() && params.symbol.len() <= MAX_SYMBOL_LENGTH,
-            PumpError::InvalidTokenSymbol
+            PumpCloneError::TokenSymbolTooLong
         );
 
         // Validate URI
         require!(
             !params.uri.is_empty() && params.uri.len() <= MAX_URI_LENGTH,
-            PumpError::InvalidTokenUri
+            PumpCloneError::InvalidMetadataUri
         );
 
-        // Validate bonding curve parameters
+        // An all-zero root is how the curve records "no allowlist"
         require!(
-            params.initial_virtual_token_reserves > 0,
-            PumpError::InvalidVirtualTokenReserves
+            params.allowlist_root != Some([0; 32]),
+            PumpCloneError::InvalidConfigurationParams
         );
 
-        require!(
-            params.initial_virtual_sol_reserves > 0,
-            PumpError::InvalidVirtualSolReserves
-        );
+        // Validate presale window and allocation
+        if let Some(presale) = &params.presale {
+            require!(
+                presale.duration >= BondingCurve::MIN_PRESALE_DURATION,
+                PumpCloneError::LaunchDurationTooShort
+            );
 
-        require!(
-            params.initial_real_token_reserves > 0,
-            PumpError::InvalidRealTokenReserves
-        );
+            require!(
+                presale.duration <= BondingCurve::MAX_PRESALE_DURATION,
+                PumpCloneError::LaunchDurationTooLong
+            );
 
-        // Ensure initial real token reserves don't exceed total supply
-        require!(
-            params.initial_real_token_reserves <= TOTAL_SUPPLY,
-            PumpError::ExceedsTotalSupply
-        );
+            require!(
+                presale.allocation > 0
+                    && presale.allocation <= self.global_state.initial_real_token_reserves,
+                PumpCloneError::InvalidPresaleParams
+            );
+        }
+
+        // Validate refund deadline
+        if let Some(duration) = params.deadline_duration {
+            require!(
+                duration >= BondingCurve::MIN_DEADLINE_DURATION,
+                PumpCloneError::LaunchDurationTooShort
+            );
+
+            require!(
+                duration <= BondingCurve::MAX_DEADLINE_DURATION,
+                PumpCloneError::LaunchDurationTooLong
+            );
+
+            if let Some(presale) = &params.presale {
+                require!(duration > presale.duration, PumpCloneError::InvalidPresaleParams);
+            }
+
+            // Refunds split the reserves over tokens sold by the curve, so
+            // tokens minted outside it must not be able to claim a share
+            require!(
+                params.creator_allocation.is_none(),
+                PumpCloneError::InvalidConfigurationParams
+            );
+        }
+
+        // Validate creator allocation and vesting schedule
+        if let Some(allocation) = &params.creator_allocation {
+            require!(
+                allocation.bps > 0
+                    && allocation.bps <= self.global_state.max_creator_allocation_bps,
+                PumpCloneError::CreatorAllocationTooLarge
+            );
 
-        // Validate bonding curve math
-        let k = params
-            .initial_virtual_token_reserves
-            .checked_mul(params.initial_virtual_sol_reserves)
-            .ok_or(PumpError::MathOverflow)?;
+            require!(
+                allocation.vesting_duration > 0
+                    && allocation.cliff_duration >= 0
+                    && allocation.cliff_duration <= allocation.vesting_duration,
+                PumpCloneError::InvalidVestingSchedule
+            );
 
-        require!(k > 0, PumpError::InvalidBondingCurveParameters);
+            let allocation_amount = creator_allocation_amount(allocation.bps)?;
+            let minted = self
+                .global_state
+                .initial_real_token_reserves
+                .checked_add(allocation_amount)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+            require!(minted <= TOTAL_SUPPLY, PumpCloneError::MaxSupplyReached);
+            require!(
+                self.vesting_escrow.is_some() && self.vesting_token_account.is_some(),
+                PumpCloneError::InvalidAccount
+            );
+        }
 
         Ok(())
     }
 
-    pub fn create_token_metadata(&self, params: &CreateTokenParams) -> Result<()> {
+    pub fn create_token_metadata(
+        &self,
+        params: &CreateTokenParams,
+        bonding_curve_bump: u8,
+    ) -> Result<()> {
         let metadata_ctx = CpiContext::new(
             self.token_metadata_program.to_account_info(),
             CreateMetadataAccountsV3 {
@@ -146,7 +279,6 @@ impl<'info> CreateToken<'info> {
             },
         );
 
-        let bonding_curve_bump = ctx.bumps.bonding_curve;
         let mint_key = self.mint.key();
         let seeds = &[
             BONDING_CURVE_SEED,
@@ -176,7 +308,7 @@ impl<'info> CreateToken<'info> {
         Ok(())
     }
 
-    pub fn mint_initial_tokens(&self, params: &CreateTokenParams) -> Result<()> {
+    pub fn mint_initial_tokens(&self, bonding_curve_bump: u8) -> Result<()> {
         let mint_ctx = CpiContext::new(
             self.token_program.to_account_info(),
             MintTo {
@@ -186,7 +318,6 @@ impl<'info> CreateToken<'info> {
             },
         );
 
-        let bonding_curve_bump = ctx.bumps.bonding_curve;
         let mint_key = self.mint.key();
         let seeds = &[
             BONDING_CURVE_SEED,
@@ -197,104 +328,254 @@ impl<'info> CreateToken<'info> {
 
         mint_to(
             mint_ctx.with_signer(signer_seeds),
-            params.initial_real_token_reserves,
+            self.global_state.initial_real_token_reserves,
         )?;
 
         Ok(())
     }
 
-    pub fn initialize_bonding_curve(&mut self, params: &CreateTokenParams) -> Result<()> {
-        let clock = Clock::get()?;
-        
-        self.bonding_curve.set_inner(BondingCurve {
+    pub fn initialize_token_metadata(
+        &mut self,
+        params: &CreateTokenParams,
+        token_metadata_bump: u8,
+    ) -> Result<()> {
+        let token_metadata = TokenMetadata {
             mint: self.mint.key(),
             creator: self.creator.key(),
-            virtual_token_reserves: params.initial_virtual_token_reserves,
-            virtual_sol_reserves: params.initial_virtual_sol_reserves,
-            real_token_reserves: params.initial_real_token_reserves,
-            real_sol_reserves: 0,
-            token_total_supply: TOTAL_SUPPLY,
-            complete: false,
-            created_at: clock.unix_timestamp,
-            bump: ctx.bumps.bonding_curve,
-        });
+            name: params.name.clone(),
+            symbol: params.symbol.clone(),
+            description: params.description.clone(),
+            image_uri: params.image_uri.clone(),
+            metadata_uri: params.uri.clone(),
+            twitter: params.twitter.clone(),
+            telegram: params.telegram.clone(),
+            website: params.website.clone(),
+            created_at: Clock::get()?.unix_timestamp,
+            market_cap: 0,
+            reply_count: 0,
+            king_of_hill_timestamp: 0,
+            nsfw: params.nsfw,
+            show_name: params.show_name,
+            bump: token_metadata_bump,
+            version: TokenMetadata::VERSION,
+            reserved: [0; 64],
+        };
+        token_metadata.validate_metadata()?;
+
+        self.token_metadata.set_inner(token_metadata);
 
         Ok(())
     }
 
-    pub fn transfer_creation_fee(&self) -> Result<()> {
-        let creation_fee = self.global_config.token_creation_fee;
-        
-        if creation_fee > 0 {
-            let transfer_instruction = anchor_lang::system_program::Transfer {
-                from: self.creator.to_account_info(),
-                to: self.fee_recipient.to_account_info(),
-            };
-
-            let cpi_ctx = CpiContext::new(
-                self.system_program.to_account_info(),
-                transfer_instruction,
-            );
+    pub fn initialize_price_history(&mut self, price_history_bump: u8) -> Result<()> {
+        let mut price_history = self.price_history.load_init()?;
+        price_history.mint = self.mint.key();
+        price_history.bump = price_history_bump;
 
-            anchor_lang::system_program::transfer(cpi_ctx, creation_fee)?;
-        }
+        Ok(())
+    }
+
+    pub fn create_vesting_escrow(
+        &mut self,
+        allocation: &CreatorAllocationParams,
+        bonding_curve_bump: u8,
+        escrow_bump: u8,
+    ) -> Result<u64> {
+        let (Some(vesting_escrow), Some(vesting_token_account)) =
+            (&mut self.vesting_escrow, &self.vesting_token_account)
+        else {
+            return err!(PumpCloneError::InvalidAccount);
+        };
+
+        let total_amount = creator_allocation_amount(allocation.bps)?;
+        let start_timestamp = Clock::get()?.unix_timestamp;
+
+        vesting_escrow.set_inner(VestingEscrow {
+            mint: self.mint.key(),
+            beneficiary: self.creator.key(),
+            total_amount,
+            claimed_amount: 0,
+            start_timestamp,
+            cliff_timestamp: start_timestamp
+                .checked_add(allocation.cliff_duration)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?,
+            end_timestamp: start_timestamp
+                .checked_add(allocation.vesting_duration)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?,
+            bump: escrow_bump,
+        });
+
+        let mint_key = self.mint.key();
+        let seeds = &[
+            BONDING_CURVE_SEED,
+            mint_key.as_ref(),
+            &[bonding_curve_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: vesting_token_account.to_account_info(),
+                    authority: self.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            total_amount,
+        )?;
+
+        Ok(total_amount)
+    }
+
+    pub fn initialize_bonding_curve(
+        &mut self,
+        params: &CreateTokenParams,
+        bonding_curve_bump: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let (presale_merkle_root, presale_allocation, presale_ends_at) = match &params.presale {
+            Some(presale) => (
+                presale.merkle_root,
+                presale.allocation,
+                clock
+                    .unix_timestamp
+                    .checked_add(presale.duration)
+                    .ok_or(PumpCloneError::ArithmeticOverflow)?,
+            ),
+            None => ([0u8; 32], 0, 0),
+        };
+
+        let deadline = match params.deadline_duration {
+            Some(duration) => clock
+                .unix_timestamp
+                .checked_add(duration)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?,
+            None => 0,
+        };
+        
+        *self.bonding_curve.load_init()? = BondingCurve {
+            mint: self.mint.key(),
+            creator: self.creator.key(),
+            allowlist_root: params.allowlist_root.unwrap_or_default(),
+            presale_merkle_root,
+            virtual_token_reserves: self.global_state.initial_virtual_token_reserves,
+            virtual_sol_reserves: self.global_state.initial_virtual_sol_reserves,
+            real_token_reserves: self.global_state.initial_real_token_reserves,
+            real_sol_reserves: 0,
+            token_total_supply: TOTAL_SUPPLY,
+            created_at: clock.unix_timestamp,
+            presale_allocation,
+            presale_tokens_sold: 0,
+            presale_sol_raised: 0,
+            presale_ends_at,
+            last_price_update: clock.unix_timestamp,
+            trade_count: 0,
+            deadline,
+            initial_real_token_reserves: self.global_state.initial_real_token_reserves,
+            refund_sol_pool: 0,
+            refund_token_supply: 0,
+            tokens_refunded: 0,
+            price_cumulative: [0; 16],
+            complete: 0,
+            presale_finalized: 0,
+            bump: bonding_curve_bump,
+            version: BondingCurve::VERSION,
+            _padding: [0; 4],
+            reserved: [0; 64],
+        };
 
         Ok(())
     }
 }
 
 pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
-    let create_token = &mut ctx.accounts;
+    let create_token = &mut *ctx.accounts;
 
     // Validate input parameters
     create_token.validate(&params)?;
 
-    // Transfer creation fee
-    create_token.transfer_creation_fee()?;
-
     // Create token metadata
-    create_token.create_token_metadata(&params)?;
+    create_token.create_token_metadata(&params, ctx.bumps.bonding_curve)?;
 
     // Mint initial tokens to bonding curve
-    create_token.mint_initial_tokens(&params)?;
+    create_token.mint_initial_tokens(ctx.bumps.bonding_curve)?;
 
     // Initialize bonding curve state
-    create_token.initialize_bonding_curve(&params)?;
+    create_token.initialize_bonding_curve(&params, ctx.bumps.bonding_curve)?;
+
+    // Store the social metadata shown on the token page
+    create_token.initialize_token_metadata(&params, ctx.bumps.token_metadata)?;
+    create_token.initialize_price_history(ctx.bumps.price_history)?;
+
+    // Count the launch on the creator's profile
+    let creator_key = create_token.creator.key();
+    create_token.user_stats.ensure_initialized(creator_key, ctx.bumps.user_stats);
+    create_token.user_stats.record_token_created()?;
+
+    // Lock the creator allocation in its vesting escrow
+    let vesting_created = match &params.creator_allocation {
+        Some(allocation) => {
+            let escrow_bump = ctx.bumps.vesting_escrow.ok_or(PumpCloneError::InvalidAccount)?;
+            let total_amount = create_token.create_vesting_escrow(
+                allocation,
+                ctx.bumps.bonding_curve,
+                escrow_bump,
+            )?;
+            let vesting_escrow = create_token
+                .vesting_escrow
+                .as_ref()
+                .ok_or(PumpCloneError::InvalidAccount)?;
+
+            Some(VestingEscrowCreated {
+                version: EVENT_VERSION,
+                mint: create_token.mint.key(),
+                beneficiary: create_token.creator.key(),
+                vesting_escrow: vesting_escrow.key(),
+                total_amount,
+                cliff_timestamp: vesting_escrow.cliff_timestamp,
+                end_timestamp: vesting_escrow.end_timestamp,
+            })
+        }
+        None => None,
+    };
+
+    msg!(
+        "Token created successfully: mint={}, creator={}, bonding_curve={}",
+        create_token.mint.key(),
+        create_token.creator.key(),
+        create_token.bonding_curve.key()
+    );
 
     // Emit token creation event
-    emit!(TokenCreated {
+    let token_created = TokenCreated {
+        version: EVENT_VERSION,
         mint: create_token.mint.key(),
         creator: create_token.creator.key(),
         name: params.name,
         symbol: params.symbol,
         uri: params.uri,
         bonding_curve: create_token.bonding_curve.key(),
-        virtual_token_reserves: params.initial_virtual_token_reserves,
-        virtual_sol_reserves: params.initial_virtual_sol_reserves,
-        real_token_reserves: params.initial_real_token_reserves,
+        virtual_token_reserves: create_token.global_state.initial_virtual_token_reserves,
+        virtual_sol_reserves: create_token.global_state.initial_virtual_sol_reserves,
+        real_token_reserves: create_token.global_state.initial_real_token_reserves,
         timestamp: Clock::get()?.unix_timestamp,
-    });
+    };
+    emit_event!(ctx, token_created);
 
-    msg!(
-        "Token created successfully: mint={}, creator={}, bonding_curve={}",
-        create_token.mint.key(),
-        create_token.creator.key(),
-        create_token.bonding_curve.key()
-    );
+    if let Some(event) = vesting_created {
+        emit_event!(ctx, event);
+    }
 
     Ok(())
 }
 
-#[event]
-pub struct TokenCreated {
-    pub mint: Pubkey,
-    pub creator: Pubkey,
-    pub name: String,
-    pub symbol: String,
-    pub uri: String,
-    pub bonding_curve: Pubkey,
-    pub virtual_token_reserves: u64,
-    pub virtual_sol_reserves: u64,
-    pub real_token_reserves: u64,
-    pub timestamp: i64,
+fn creator_allocation_amount(bps: u16) -> Result<u64> {
+    TOTAL_SUPPLY
+        .checked_mul(bps as u64)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(PumpCloneError::ArithmeticOverflow.into())
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{CommentDeleted, EVENT_VERSION},
+    state::{Comment, CommentLike, TokenMetadata},
+    utils::{close_account, load_account},
+};
+
+/// Every like on the comment is closed along with it, refunding each liker's
+/// rent. The likes are passed in `remaining_accounts` as
+/// `(comment_like, liker)` pairs, one pair per like.
+#[derive(Accounts)]
+pub struct DeleteComment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata", token_metadata.mint.as_ref()],
+        bump = token_metadata.bump
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ PumpCloneError::AccessControlViolation,
+        constraint = comment.mint == token_metadata.mint @ PumpCloneError::InvalidAccount
+    )]
+    pub comment: Account<'info, Comment>,
+
+    /// CHECK: the comment being replied to, required for replies and checked
+    /// against `comment.reply_to`. It may already have been deleted, in which
+    /// case there is no reply count left to update.
+    #[account(mut)]
+    pub parent_comment: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DeleteComment<'info>>) -> Result<()> {
+    let comment_key = ctx.accounts.comment.key();
+
+    if let Some(reply_to) = ctx.accounts.comment.reply_to {
+        let parent_comment = ctx.accounts.parent_comment
+            .as_ref()
+            .ok_or(PumpCloneError::InvalidAccount)?;
+        require_keys_eq!(parent_comment.key(), reply_to, PumpCloneError::InvalidAccount);
+
+        if !parent_comment.data_is_empty() {
+            let mut parent: Comment = load_account(parent_comment)?;
+            parent.replies = parent.replies.saturating_sub(1);
+            parent.try_serialize(&mut &mut parent_comment.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    require!(
+        ctx.remaining_accounts.len() == 2 * ctx.accounts.comment.likes as usize,
+        PumpCloneError::InvalidAccount
+    );
+
+    for accounts in ctx.remaining_accounts.chunks_exact(2) {
+        let (comment_like, liker) = (&accounts[0], &accounts[1]);
+        let like: CommentLike = load_account(comment_like)?;
+        require_keys_eq!(like.comment, comment_key, PumpCloneError::InvalidAccount);
+        require_keys_eq!(like.user, liker.key(), PumpCloneError::InvalidAccount);
+
+        // A like account's address is unique per (comment, user), so checking
+        // it also rules out the same like being passed twice
+        let address = Pubkey::create_program_address(
+            &[b"comment_like", comment_key.as_ref(), liker.key.as_ref(), &[like.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(PumpCloneError::InvalidAccount))?;
+        require_keys_eq!(comment_like.key(), address, PumpCloneError::InvalidAccount);
+
+        close_account(comment_like, liker)?;
+    }
+
+    let token_metadata = &mut ctx.accounts.token_metadata;
+    token_metadata.reply_count = token_metadata.reply_count.saturating_sub(1);
+
+    emit!(CommentDeleted {
+        version: EVENT_VERSION,
+        comment: comment_key,
+        mint: token_metadata.mint,
+        user: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{
+    admin::{self, AdminActionAccounts},
+    error::PumpCloneError,
+    events::{AdminProposalExecuted, EVENT_VERSION},
+    state::{AdminAction, AdminMultisig, AdminProposal, GlobalState},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteAdminProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_multisig"],
+        bump = admin_multisig.bump
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"admin_proposal",
+            admin_multisig.key().as_ref(),
+            &admin_proposal.index.to_le_bytes()
+        ],
+        bump = admin_proposal.bump,
+        constraint = !admin_proposal.executed @ PumpCloneError::ProposalAlreadyExecuted,
+        constraint = admin_proposal.signers_version == admin_multisig.signers_version @ PumpCloneError::StaleAdminProposal
+    )]
+    pub admin_proposal: Account<'info, AdminProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// CHECK: only used as the destination of a `WithdrawFees` or
+    /// `Withdraw` action and checked against the recipient it pays
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: the account the action operates on (blocklist PDA, bonding
+    /// curve, pending config change or account to upgrade); validated
+    /// against the action by the executor
+    #[account(mut)]
+    pub target: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: only read by a `CompleteBondingCurve` action and checked
+    /// against `global_state.oracle_feed`
+    pub oracle_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the curve's token account a `Withdraw` action empties; checked
+    /// to be the curve's associated token account
+    #[account(mut)]
+    pub bonding_curve_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the recipient's token account a `Withdraw` action pays the
+    /// curve's tokens into; checked to be its associated token account
+    #[account(mut)]
+    pub recipient_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteAdminProposal>) -> Result<()> {
+    let multisig_key = ctx.accounts.admin_multisig.key();
+
+    require!(
+        ctx.accounts.admin_multisig.signer_index(&ctx.accounts.executor.key()).is_some(),
+        PumpCloneError::UnauthorizedAdmin
+    );
+    require!(
+        ctx.accounts.admin_proposal.approval_count() >= ctx.accounts.admin_multisig.threshold,
+        PumpCloneError::MultiSigThresholdNotMet
+    );
+
+    let action = ctx.accounts.admin_proposal.action.clone();
+
+    match &action {
+        // Rotating signers only touches the multisig itself, so it doesn't
+        // depend on the multisig holding the platform authority
+        AdminAction::SetSigners { signers, threshold } => {
+            AdminMultisig::validate_signers(signers, *threshold)?;
+
+            let multisig = &mut ctx.accounts.admin_multisig;
+            multisig.signers = signers.clone();
+            multisig.threshold = *threshold;
+            // Approvals are recorded by signer index, so every proposal
+            // approved under the old set becomes stale
+            multisig.signers_version = multisig
+                .signers_version
+                .checked_add(1)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?;
+        }
+        _ => {
+            let accounts = AdminActionAccounts {
+                authority: multisig_key,
+                payer: ctx.accounts.executor.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                fee_vault_bump: ctx.bumps.fee_vault,
+                recipient: ctx.accounts.recipient.to_account_info(),
+                        target: ctx.accounts.target.as_ref().map(|target| target.to_account_info()),
+                oracle_feed: ctx.accounts.oracle_feed.as_ref().map(|feed| feed.to_account_info()),
+                bonding_curve_token_account: ctx
+                    .accounts
+                    .bonding_curve_token_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                recipient_token_account: ctx
+                    .accounts
+                    .recipient_token_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                token_program: ctx
+                    .accounts
+                    .token_program
+                    .as_ref()
+                    .map(|program| program.to_account_info()),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            };
+
+            admin::execute(
+                &mut ctx.accounts.global_state,
+                &action,
+                &accounts,
+                ctx.accounts.admin_proposal.created_at,
+            )?;
+        }
+    }
+
+    let proposal = &mut ctx.accounts.admin_proposal;
+    proposal.executed = true;
+
+    emit!(AdminProposalExecuted {
+        version: EVENT_VERSION,
+        multisig: multisig_key,
+        proposal: proposal.key(),
+        index: proposal.index,
+        executor: ctx.accounts.executor.key(),
+        action,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
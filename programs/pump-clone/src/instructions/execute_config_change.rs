@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{ConfigChangeExecuted, EVENT_VERSION},
+    state::{GlobalState, PendingConfigChange},
+};
+
+/// Permissionless once the eta has passed; the queued change was already
+/// authorized when it was published.
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"config_change", &pending_config_change.index.to_le_bytes()],
+        bump = pending_config_change.bump,
+        has_one = proposer
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// CHECK: receives the rent of the closed change, checked via has_one
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let change = &ctx.accounts.pending_config_change;
+
+    require!(timestamp >= change.eta, PumpCloneError::TimelockPeriodNotElapsed);
+
+    change.update.validate()?;
+    change.update.apply(&mut ctx.accounts.global_state);
+
+    emit!(ConfigChangeExecuted {
+        version: EVENT_VERSION,
+        index: change.index,
+        update: change.update.clone(),
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
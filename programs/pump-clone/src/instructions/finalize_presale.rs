@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, events::{PresaleFinalized, EVENT_VERSION}, state::BondingCurve};
+
+/// Permissionless: anyone can close the presale once its window has ended or
+/// the full allocation has been bought.
+#[derive(Accounts)]
+pub struct FinalizePresale<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+}
+
+pub fn handler(ctx: Context<FinalizePresale>) -> Result<()> {
+    let clock = Clock::get()?;
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+
+    require!(bonding_curve.is_presale_pending(), PumpCloneError::PresaleNotActive);
+    require!(
+        clock.unix_timestamp >= bonding_curve.presale_ends_at
+            || bonding_curve.presale_tokens_sold == bonding_curve.presale_allocation,
+        PumpCloneError::PresaleActive
+    );
+
+    bonding_curve.accumulate_price(clock.unix_timestamp)?;
+    bonding_curve.finalize_presale()?;
+
+    emit!(PresaleFinalized {
+        version: EVENT_VERSION,
+        mint: bonding_curve.mint,
+        tokens_sold: bonding_curve.presale_tokens_sold,
+        sol_raised: bonding_curve.presale_sol_raised,
+        unclaimed_tokens: bonding_curve.presale_allocation - bonding_curve.presale_tokens_sold,
+        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        virtual_token_reserves: bonding_curve.virtual_token_reserves,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
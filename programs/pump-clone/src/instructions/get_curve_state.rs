@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    curve::{CurveReserves, CurveState},
+    state::{BondingCurve, GlobalState},
+};
+
+/// Read-only view of a curve, shared by the quote instructions. These are
+/// meant to be simulated: results come back through return data.
+#[derive(Accounts)]
+pub struct CurveView<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+}
+
+pub fn handler(ctx: Context<CurveView>) -> Result<()> {
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+
+    let state = CurveState {
+        mint: bonding_curve.mint,
+        reserves: CurveReserves::from(&*bonding_curve),
+        token_total_supply: bonding_curve.token_total_supply,
+        spot_price: bonding_curve.spot_price()?,
+        market_cap: bonding_curve.market_cap()?,
+        fee_bps: ctx.accounts.global_state.platform_fee_bps,
+        complete: bonding_curve.is_complete(),
+        presale_pending: bonding_curve.is_presale_pending(),
+    };
+
+    set_return_data(&state.try_to_vec()?);
+
+    Ok(())
+}
\ No newline at end of file
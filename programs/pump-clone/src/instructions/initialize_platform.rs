@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::TOTAL_SUPPLY,
+    error::PumpCloneError,
+    events::{PlatformInitialized, EVENT_VERSION},
+    state::{ConfigUpdate, GlobalState},
+};
+
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = GlobalState::LEN,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializePlatformParams {
+    pub guardian: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub platform_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub migration_fee: u64,
+    pub min_sol_threshold: u64,
+    pub max_sol_threshold: u64,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub max_creator_allocation_bps: u16,
+    pub graduation_market_cap_usd: u64,
+    pub king_of_hill_bps: u16,
+    pub oracle_feed: Pubkey,
+    pub oracle_max_staleness: i64,
+    pub oracle_max_confidence_bps: u16,
+    pub token_expiry: i64,
+    pub config_timelock: i64,
+}
+
+impl InitializePlatformParams {
+    /// The parameters later changes go through, so initialization is held
+    /// to the same bounds as a config update.
+    fn as_config_update(&self) -> ConfigUpdate {
+        ConfigUpdate {
+            platform_fee_bps: Some(self.platform_fee_bps),
+            creator_fee_bps: Some(self.creator_fee_bps),
+            migration_fee: Some(self.migration_fee),
+            max_creator_allocation_bps: Some(self.max_creator_allocation_bps),
+            graduation_market_cap_usd: Some(self.graduation_market_cap_usd),
+            king_of_hill_bps: Some(self.king_of_hill_bps),
+            oracle_feed: Some(self.oracle_feed),
+            oracle_max_staleness: Some(self.oracle_max_staleness),
+            oracle_max_confidence_bps: Some(self.oracle_max_confidence_bps),
+            token_expiry: Some(self.token_expiry),
+            config_timelock: Some(self.config_timelock),
+        }
+    }
+}
+
+pub fn handler(ctx: Context<InitializePlatform>, params: InitializePlatformParams) -> Result<()> {
+    let update = params.as_config_update();
+    update.validate()?;
+
+    require!(
+        params.fee_recipient != Pubkey::default(),
+        PumpCloneError::InvalidFeeRecipient
+    );
+    require!(
+        params.initial_virtual_token_reserves > 0,
+        PumpCloneError::InvalidVirtualTokenReserves
+    );
+    require!(
+        params.initial_virtual_sol_reserves > 0,
+        PumpCloneError::InvalidVirtualSolReserves
+    );
+    require!(
+        params.initial_real_token_reserves > 0
+            && params.initial_real_token_reserves <= params.initial_virtual_token_reserves
+            && params.initial_real_token_reserves <= TOTAL_SUPPLY,
+        PumpCloneError::InvalidRealTokenReserves
+    );
+    require!(
+        params.min_sol_threshold <= params.max_sol_threshold,
+        PumpCloneError::InvalidConfigurationParams
+    );
+    require!(
+        params.graduation_market_cap_usd > 0,
+        PumpCloneError::InvalidConfigurationParams
+    );
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.set_inner(GlobalState {
+        authority: ctx.accounts.authority.key(),
+        pending_authority: None,
+        guardian: params.guardian,
+        fee_recipient: params.fee_recipient,
+        platform_fee_bps: 0,
+        creator_fee_bps: 0,
+        migration_fee: 0,
+        min_sol_threshold: params.min_sol_threshold,
+        max_sol_threshold: params.max_sol_threshold,
+        initial_virtual_token_reserves: params.initial_virtual_token_reserves,
+        initial_virtual_sol_reserves: params.initial_virtual_sol_reserves,
+        initial_real_token_reserves: params.initial_real_token_reserves,
+        max_creator_allocation_bps: 0,
+        graduation_market_cap_usd: 0,
+        king_of_hill_bps: 0,
+        oracle_feed: Pubkey::default(),
+        oracle_max_staleness: 0,
+        oracle_max_confidence_bps: 0,
+        token_expiry: 0,
+        config_timelock: 0,
+        config_change_count: 0,
+        pause_flags: 0,
+        bump: ctx.bumps.global_state,
+        version: GlobalState::VERSION,
+        reserved: [0; 64],
+    });
+    update.apply(global_state);
+
+    // Fees are paid into the vault as plain transfers, which the runtime
+    // rejects while the vault is below the rent-exempt minimum
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let top_up = rent_exempt_minimum.saturating_sub(ctx.accounts.fee_vault.lamports());
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    emit!(PlatformInitialized {
+        version: EVENT_VERSION,
+        authority: ctx.accounts.authority.key(),
+        guardian: params.guardian,
+        fee_recipient: params.fee_recipient,
+        platform_fee_bps: params.platform_fee_bps,
+        graduation_market_cap_usd: params.graduation_market_cap_usd,
+        oracle_feed: params.oracle_feed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
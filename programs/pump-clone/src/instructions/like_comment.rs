@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{CommentLiked, EVENT_VERSION},
+    state::{Comment, CommentLike},
+};
+
+/// The like PDA is unique per (comment, user), so a second like from the
+/// same wallet fails at account creation.
+#[derive(Accounts)]
+pub struct LikeComment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub comment: Account<'info, Comment>,
+
+    #[account(
+        init,
+        payer = user,
+        space = CommentLike::LEN,
+        seeds = [b"comment_like", comment.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub comment_like: Account<'info, CommentLike>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<LikeComment>) -> Result<()> {
+    let comment = &mut ctx.accounts.comment;
+    comment.likes = comment.likes
+        .checked_add(1)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    ctx.accounts.comment_like.set_inner(CommentLike {
+        comment: comment.key(),
+        user: ctx.accounts.user.key(),
+        bump: ctx.bumps.comment_like,
+    });
+
+    emit!(CommentLiked {
+        version: EVENT_VERSION,
+        comment: comment.key(),
+        user: ctx.accounts.user.key(),
+        likes: comment.likes,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,17 +1,76 @@
+// Every instruction module exports a `handler`; the #[program] module calls
+// them by path, and the globs only exist to re-export the account structs.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod create_token;
-pub mod buy_token;
-pub mod sell_token;
+pub mod buy_tokens;
+pub mod sell_tokens;
+pub mod complete_bonding_curve;
+pub mod withdraw;
 pub mod initialize_platform;
-pub mod update_platform;
-pub mod withdraw_fees;
-pub mod migrate_to_raydium;
 pub mod close_token;
+pub mod block_wallet;
+pub mod unblock_wallet;
+pub mod clear_allowlist;
+pub mod presale_buy;
+pub mod finalize_presale;
+pub mod claim_vested;
+pub mod pause;
+pub mod unpause;
+pub mod set_guardian;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod set_fee_recipient;
+pub mod create_admin_multisig;
+pub mod create_admin_proposal;
+pub mod approve_admin_proposal;
+pub mod execute_admin_proposal;
+pub mod queue_config_change;
+pub mod execute_config_change;
+pub mod cancel_config_change;
+pub mod post_comment;
+pub mod reply_comment;
+pub mod like_comment;
+pub mod delete_comment;
+pub mod update_social_links;
+pub mod get_curve_state;
+pub mod quote_buy;
+pub mod quote_sell;
+pub mod quote_buy_exact_out;
+pub mod refund;
+pub mod upgrade_account;
 
 pub use create_token::*;
-pub use buy_token::*;
-pub use sell_token::*;
+pub use buy_tokens::*;
+pub use sell_tokens::*;
+pub use complete_bonding_curve::*;
+pub use withdraw::*;
 pub use initialize_platform::*;
-pub use update_platform::*;
-pub use withdraw_fees::*;
-pub use migrate_to_raydium::*;
-pub use close_token::*;
\ No newline at end of file
+pub use close_token::*;
+pub use block_wallet::*;
+pub use unblock_wallet::*;
+pub use clear_allowlist::*;
+pub use presale_buy::*;
+pub use finalize_presale::*;
+pub use claim_vested::*;
+pub use pause::*;
+pub use unpause::*;
+pub use set_guardian::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_fee_recipient::*;
+pub use create_admin_multisig::*;
+pub use create_admin_proposal::*;
+pub use approve_admin_proposal::*;
+pub use execute_admin_proposal::*;
+pub use queue_config_change::*;
+pub use execute_config_change::*;
+pub use cancel_config_change::*;
+pub use post_comment::*;
+pub use reply_comment::*;
+pub use like_comment::*;
+pub use delete_comment::*;
+pub use update_social_links::*;
+pub use get_curve_state::*;
+pub use refund::*;
+pub use upgrade_account::*;
\ No newline at end of file
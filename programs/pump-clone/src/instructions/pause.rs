@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, events::{PauseFlagsUpdated, EVENT_VERSION}, state::GlobalState};
+
+/// Either the authority or the guardian can pause. Pausing only ever adds
+/// scopes, so a compromised guardian key can halt the platform but cannot
+/// reopen it or touch fees.
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        constraint = pauser.key() == global_state.authority
+            || pauser.key() == global_state.guardian @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<Pause>, scopes: u8) -> Result<()> {
+    require!(
+        scopes != 0 && scopes & !GlobalState::PAUSE_ALL == 0,
+        PumpCloneError::InvalidConfigurationParams
+    );
+
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_flags = global_state.pause_flags;
+    global_state.pause_flags |= scopes;
+
+    emit!(PauseFlagsUpdated {
+        version: EVENT_VERSION,
+        previous_flags,
+        pause_flags: global_state.pause_flags,
+        updated_by: ctx.accounts.pauser.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
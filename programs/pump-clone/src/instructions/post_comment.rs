@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{CommentPosted, EVENT_VERSION},
+    state::{Comment, TokenMetadata},
+};
+
+#[derive(Accounts)]
+#[instruction(comment_id: u64)]
+pub struct PostComment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata", token_metadata.mint.as_ref()],
+        bump = token_metadata.bump
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Comment::LEN,
+        seeds = [
+            b"comment",
+            token_metadata.mint.as_ref(),
+            user.key().as_ref(),
+            &comment_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub comment: Account<'info, Comment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PostComment>, _comment_id: u64, content: String) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let token_metadata = &mut ctx.accounts.token_metadata;
+
+    let comment = Comment {
+        user: ctx.accounts.user.key(),
+        mint: token_metadata.mint,
+        content,
+        timestamp,
+        reply_to: None,
+        likes: 0,
+        replies: 0,
+        bump: ctx.bumps.comment,
+        version: Comment::VERSION,
+        reserved: [0; 32],
+    };
+    comment.validate_content()?;
+
+    token_metadata.reply_count = token_metadata.reply_count
+        .checked_add(1)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    emit!(CommentPosted {
+        version: EVENT_VERSION,
+        comment: ctx.accounts.comment.key(),
+        mint: comment.mint,
+        user: comment.user,
+        reply_to: None,
+        content: comment.content.clone(),
+        timestamp,
+    });
+
+    ctx.accounts.comment.set_inner(comment);
+
+    Ok(())
+}
\ No newline at end of file
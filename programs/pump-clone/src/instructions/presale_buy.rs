@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::PumpCloneError,
+    events::{PresalePurchased, EVENT_VERSION},
+    state::{BondingCurve, GlobalState, Position, PresaleReceipt, UserStats},
+    utils::{presale_leaf, require_not_blocked, verify_merkle_proof},
+};
+
+#[derive(Accounts)]
+pub struct PresaleBuy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused(GlobalState::PAUSE_BUY) @ PumpCloneError::EmergencyPauseActivated
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = mint
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = PresaleReceipt::LEN,
+        seeds = [b"presale_receipt", mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub presale_receipt: Account<'info, PresaleReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = UserStats::LEN,
+        seeds = [b"user_stats", buyer.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = Position::LEN,
+        seeds = [b"position", buyer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: blocklist PDA for the buyer; the purchase is rejected if it has been initialized
+    #[account(
+        seeds = [b"blocked_wallet", buyer.key().as_ref()],
+        bump
+    )]
+    pub blocked_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PresaleBuy>,
+    token_amount: u64,
+    max_sol_cost: u64,
+    allocation: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require_not_blocked(&ctx.accounts.blocked_wallet)?;
+
+    let clock = Clock::get()?;
+    let buyer = ctx.accounts.buyer.key();
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+
+    require!(
+        bonding_curve.is_presale_pending() && clock.unix_timestamp < bonding_curve.presale_ends_at,
+        PumpCloneError::PresaleNotActive
+    );
+    require!(token_amount > 0, PumpCloneError::InvalidPurchaseAmount);
+    require!(
+        verify_merkle_proof(
+            &proof,
+            bonding_curve.presale_merkle_root,
+            presale_leaf(&buyer, allocation)
+        ),
+        PumpCloneError::WalletNotAllowlisted
+    );
+
+    // Per-wallet allocation and the launch-wide presale cap
+    let wallet_purchased = ctx.accounts.presale_receipt.tokens_purchased
+        .checked_add(token_amount)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    require!(wallet_purchased <= allocation, PumpCloneError::PresaleAllocationExceeded);
+
+    let presale_tokens_sold = bonding_curve.presale_tokens_sold
+        .checked_add(token_amount)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    require!(
+        presale_tokens_sold <= bonding_curve.presale_allocation,
+        PumpCloneError::PresaleAllocationExceeded
+    );
+    require!(
+        token_amount <= bonding_curve.real_token_reserves,
+        PumpCloneError::InsufficientTokensForSale
+    );
+
+    let sol_cost = bonding_curve.calculate_presale_cost(token_amount)?;
+    let fee_amount = sol_cost
+        .checked_mul(ctx.accounts.global_state.platform_fee_bps as u64)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(PumpCloneError::DivisionByZero)?;
+    let total_cost = sol_cost
+        .checked_add(fee_amount)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    require!(total_cost <= max_sol_cost, PumpCloneError::SlippageToleranceExceeded);
+
+    // The curve signs the token transfer below, so its data can't stay borrowed
+    let curve_bump = bonding_curve.bump;
+    drop(bonding_curve);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.bonding_curve.to_account_info(),
+            },
+        ),
+        sol_cost,
+    )?;
+
+    if fee_amount > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
+            ),
+            fee_amount,
+        )?;
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        b"bonding_curve",
+        mint_key.as_ref(),
+        &[curve_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        token_amount,
+    )?;
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
+        .checked_sub(token_amount)
+        .ok_or(PumpCloneError::ArithmeticUnderflow)?;
+    bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
+        .checked_add(sol_cost)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    bonding_curve.presale_tokens_sold = presale_tokens_sold;
+    bonding_curve.presale_sol_raised = bonding_curve.presale_sol_raised
+        .checked_add(sol_cost)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    bonding_curve.record_trade()?;
+
+    let receipt = &mut ctx.accounts.presale_receipt;
+    receipt.buyer = buyer;
+    receipt.mint = mint_key;
+    receipt.tokens_purchased = wallet_purchased;
+    receipt.sol_spent = receipt.sol_spent
+        .checked_add(sol_cost)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    receipt.bump = ctx.bumps.presale_receipt;
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.ensure_initialized(buyer, ctx.bumps.user_stats);
+    user_stats.update_buy_stats(token_amount, total_cost, fee_amount, clock.unix_timestamp)?;
+
+    let position = &mut ctx.accounts.position;
+    position.ensure_initialized(buyer, ctx.accounts.mint.key(), ctx.bumps.position, clock.unix_timestamp);
+    position.record_buy(token_amount, total_cost, clock.unix_timestamp)?;
+
+    emit!(PresalePurchased {
+        version: EVENT_VERSION,
+        mint: mint_key,
+        buyer,
+        token_amount,
+        sol_cost,
+        fee_amount,
+        allocation_remaining: allocation - wallet_purchased,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, events::{AuthorityProposed, EVENT_VERSION}, state::GlobalState};
+
+/// First half of an authority transfer. Nothing changes until the proposed
+/// key signs `accept_authority`, so a mistyped address can simply be
+/// replaced by proposing again.
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    require!(
+        new_authority != Pubkey::default() && new_authority != ctx.accounts.authority.key(),
+        PumpCloneError::InvalidConfigurationParams
+    );
+
+    ctx.accounts.global_state.pending_authority = Some(new_authority);
+
+    emit!(AuthorityProposed {
+        version: EVENT_VERSION,
+        authority: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
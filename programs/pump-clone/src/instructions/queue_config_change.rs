@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{ConfigChangeQueued, EVENT_VERSION},
+    state::{ConfigUpdate, GlobalState, PendingConfigChange},
+};
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PendingConfigChange::LEN,
+        seeds = [b"config_change".as_ref(), &global_state.config_change_count.to_le_bytes()],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueConfigChange>, update: ConfigUpdate) -> Result<()> {
+    update.validate()?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    let index = global_state.config_change_count;
+    global_state.config_change_count = global_state.config_change_count
+        .checked_add(1)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    let queued_at = Clock::get()?.unix_timestamp;
+    let eta = queued_at
+        .checked_add(global_state.config_timelock)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+    ctx.accounts.pending_config_change.set_inner(PendingConfigChange {
+        index,
+        proposer: ctx.accounts.authority.key(),
+        update: update.clone(),
+        queued_at,
+        eta,
+        bump: ctx.bumps.pending_config_change,
+    });
+
+    emit!(ConfigChangeQueued {
+        version: EVENT_VERSION,
+        index,
+        proposer: ctx.accounts.authority.key(),
+        update,
+        eta,
+        timestamp: queued_at,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    curve::{self, CurveReserves},
+    error::PumpCloneError,
+};
+
+use super::get_curve_state::CurveView;
+
+/// Simulate to quote the SOL needed to receive exactly `token_amount` tokens.
+pub fn handler(ctx: Context<CurveView>, token_amount: u64) -> Result<()> {
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+
+    require!(!bonding_curve.is_complete(), PumpCloneError::TokenAlreadyGraduated);
+    require!(!bonding_curve.is_presale_pending(), PumpCloneError::PresaleActive);
+
+    let quote = curve::quote_buy_exact_out(
+        &CurveReserves::from(&*bonding_curve),
+        token_amount,
+        ctx.accounts.global_state.platform_fee_bps,
+    )?;
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    error::PumpCloneError,
+    events::{emit_event, RefundClaimed, EVENT_VERSION},
+    state::BondingCurve,
+};
+
+/// Returns tokens to a curve that missed its deadline for a pro-rata share
+/// of the SOL it raised.
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = mint
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Refund>, token_amount: u64) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    require!(
+        ctx.accounts.bonding_curve.load()?.is_past_deadline(timestamp),
+        PumpCloneError::CurveDeadlineNotReached
+    );
+    require!(token_amount > 0, PumpCloneError::InvalidSaleAmount);
+    require!(
+        ctx.accounts.user_token_account.amount >= token_amount,
+        PumpCloneError::InsufficientTokenBalance
+    );
+
+    let sol_amount = ctx.accounts.bonding_curve.load_mut()?.refund(token_amount)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+
+    **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+
+    emit_event!(ctx, RefundClaimed {
+        version: EVENT_VERSION,
+        mint: ctx.accounts.mint.key(),
+        user: ctx.accounts.user.key(),
+        token_amount,
+        sol_amount,
+        timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,180 +1,219 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::*;
-use crate::errors::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
+use crate::{
+    constants::BONDING_CURVE_SEED,
+    curve::{self, CurveReserves},
+    error::PumpCloneError,
+    events::{emit_event, TradeEvent, EVENT_VERSION},
+    oracle::sol_usd_price,
+    state::{BondingCurve, GlobalState, KingOfTheHill, Position, PriceHistory, TokenMetadata, UserStats},
+    utils::{record_price, require_not_blocked, track_market_cap},
+};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct SellTokens<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
-    
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused(GlobalState::PAUSE_SELL) @ PumpCloneError::EmergencyPauseActivated
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = mint
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
     #[account(
         mut,
-        seeds = [b"token_launch", token_launch.creator.as_ref(), token_launch.mint.as_ref()],
-        bump = token_launch.bump,
-        constraint = token_launch.is_active @ PumpError::LaunchNotActive,
-        constraint = !token_launch.migrated @ PumpError::AlreadyMigrated
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve
     )]
-    pub token_launch: Account<'info, TokenLaunch>,
-    
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
     #[account(
         mut,
-        constraint = seller_token_account.mint == token_launch.mint @ PumpError::InvalidMint,
-        constraint = seller_token_account.owner == seller.key() @ PumpError::InvalidOwner
+        token::mint = mint,
+        token::authority = seller
     )]
     pub seller_token_account: Account<'info, TokenAccount>,
-    
+
     #[account(
         mut,
-        seeds = [b"bonding_curve", token_launch.key().as_ref()],
-        bump = bonding_curve.bump
+        seeds = [b"token_metadata", mint.key().as_ref()],
+        bump = token_metadata.bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
-    
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = KingOfTheHill::LEN,
+        seeds = [b"king_of_the_hill"],
+        bump
+    )]
+    pub king_of_the_hill: Account<'info, KingOfTheHill>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = UserStats::LEN,
+        seeds = [b"user_stats", seller.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = Position::LEN,
+        seeds = [b"position", seller.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
     #[account(
         mut,
-        constraint = curve_token_account.mint == token_launch.mint @ PumpError::InvalidMint,
-        constraint = curve_token_account.owner == bonding_curve.key() @ PumpError::InvalidOwner
+        seeds = [b"price_history", mint.key().as_ref()],
+        bump = price_history.load()?.bump
     )]
-    pub curve_token_account: Account<'info, TokenAccount>,
-    
+    pub price_history: AccountLoader<'info, PriceHistory>,
+
+    /// CHECK: validated against `global_state.oracle_feed` and read through
+    /// the `Oracle` trait
+    #[account(address = global_state.oracle_feed @ PumpCloneError::InvalidPriceFeed)]
+    pub oracle_feed: UncheckedAccount<'info>,
+
+    /// CHECK: blocklist PDA for the seller; trading is rejected if it has been initialized
+    #[account(
+        seeds = [b"blocked_wallet", seller.key().as_ref()],
+        bump
+    )]
+    pub blocked_wallet: UncheckedAccount<'info>,
+
     #[account(
         mut,
-        constraint = curve_sol_account.owner == bonding_curve.key() @ PumpError::InvalidOwner
+        seeds = [b"fee_vault"],
+        bump
     )]
-    pub curve_sol_account: SystemAccount<'info>,
-    
+    pub fee_vault: SystemAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64) -> Result<()> {
-    let token_launch = &mut ctx.accounts.token_launch;
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    let seller = &ctx.accounts.seller;
-    let seller_token_account = &ctx.accounts.seller_token_account;
-    let curve_token_account = &ctx.accounts.curve_token_account;
-    let curve_sol_account = &ctx.accounts.curve_sol_account;
+pub fn handler(ctx: Context<SellTokens>, token_amount: u64, min_sol_out: u64) -> Result<()> {
+    require_not_blocked(&ctx.accounts.blocked_wallet)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let seller = ctx.accounts.seller.key();
+    let mint = ctx.accounts.mint.key();
 
-    // Validate token amount
-    require!(token_amount > 0, PumpError::InvalidAmount);
     require!(
-        seller_token_account.amount >= token_amount,
-        PumpError::InsufficientTokenBalance
+        ctx.accounts.seller_token_account.amount >= token_amount,
+        PumpCloneError::InsufficientTokenBalance
     );
 
-    // Calculate SOL amount to return based on bonding curve
-    let current_supply = bonding_curve.total_supply;
-    let new_supply = current_supply
-        .checked_sub(token_amount)
-        .ok_or(PumpError::MathOverflow)?;
-
-    // Calculate SOL amount using bonding curve formula
-    let sol_amount = calculate_sell_price(current_supply, new_supply, bonding_curve.k)?;
-    
-    // Apply sell fee
-    let fee_amount = sol_amount
-        .checked_mul(bonding_curve.sell_fee_bps as u64)
-        .ok_or(PumpError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let net_sol_amount = sol_amount
-        .checked_sub(fee_amount)
-        .ok_or(PumpError::MathOverflow)?;
-
-    // Validate curve has enough SOL
+    let bonding_curve = ctx.accounts.bonding_curve.load()?;
+
+    require!(!bonding_curve.is_complete(), PumpCloneError::TokenAlreadyGraduated);
+    require!(!bonding_curve.is_presale_pending(), PumpCloneError::PresaleActive);
     require!(
-        curve_sol_account.lamports() >= sol_amount,
-        PumpError::InsufficientCurveBalance
+        !bonding_curve.is_past_deadline(timestamp),
+        PumpCloneError::CurveDeadlinePassed
     );
 
-    // Transfer tokens from seller to curve
-    let transfer_tokens_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: seller_token_account.to_account_info(),
-            to: curve_token_account.to_account_info(),
-            authority: seller.to_account_info(),
-        },
+    let reserves_before = CurveReserves::from(&*bonding_curve);
+    let quote = curve::quote_sell(
+        &reserves_before,
+        token_amount,
+        ctx.accounts.global_state.platform_fee_bps,
+    )?;
+    require!(
+        quote.net_sol_amount >= min_sol_out,
+        PumpCloneError::SlippageToleranceExceeded
     );
-    token::transfer(transfer_tokens_ctx, token_amount)?;
-
-    // Transfer SOL from curve to seller
-    let curve_seeds = &[
-        b"bonding_curve",
-        token_launch.key().as_ref(),
-        &[bonding_curve.bump],
-    ];
-    let curve_signer = &[&curve_seeds[..]];
-
-    **curve_sol_account.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
-    **seller.to_account_info().try_borrow_mut_lamports()? += net_sol_amount;
-
-    // Update bonding curve state
-    bonding_curve.total_supply = new_supply;
-    bonding_curve.sol_reserves = bonding_curve.sol_reserves
-        .checked_sub(sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
-    bonding_curve.total_fees = bonding_curve.total_fees
-        .checked_add(fee_amount)
-        .ok_or(PumpError::MathOverflow)?;
-
-    // Update token launch stats
-    token_launch.total_transactions = token_launch.total_transactions
-        .checked_add(1)
-        .ok_or(PumpError::MathOverflow)?;
-    token_launch.volume = token_launch.volume
-        .checked_add(sol_amount)
-        .ok_or(PumpError::MathOverflow)?;
-
-    // Emit sell event
-    emit!(TokenSellEvent {
-        seller: seller.key(),
-        token_launch: token_launch.key(),
+    drop(bonding_curve);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
         token_amount,
-        sol_amount: net_sol_amount,
-        fee_amount,
-        new_supply,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
+    )?;
 
-    Ok(())
-}
+    // The curve is owned by this program, so its reserves are paid out by
+    // moving lamports directly
+    let curve_info = ctx.accounts.bonding_curve.to_account_info();
+    **curve_info.try_borrow_mut_lamports()? -= quote.sol_amount;
+    **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += quote.net_sol_amount;
+    **ctx.accounts.fee_vault.to_account_info().try_borrow_mut_lamports()? += quote.fee_amount;
 
-fn calculate_sell_price(current_supply: u64, new_supply: u64, k: u64) -> Result<u64> {
-    // Bonding curve formula: price = k * supply
-    // Integral from new_supply to current_supply: k * (current_supply^2 - new_supply^2) / 2
-    
-    let current_supply_squared = (current_supply as u128)
-        .checked_mul(current_supply as u128)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let new_supply_squared = (new_supply as u128)
-        .checked_mul(new_supply as u128)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let supply_diff = current_supply_squared
-        .checked_sub(new_supply_squared)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    let sol_amount = (k as u128)
-        .checked_mul(supply_diff)
-        .ok_or(PumpError::MathOverflow)?
-        .checked_div(2)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    // Convert back to u64, ensuring no overflow
-    u64::try_from(sol_amount).map_err(|_| PumpError::MathOverflow.into())
-}
+    // The price only drives graduation and king of the hill, so a stale or
+    // unreadable feed defers those rather than failing the trade
+    let sol_usd_price = sol_usd_price(&ctx.accounts.oracle_feed, &ctx.accounts.global_state).ok();
+
+    let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+    bonding_curve.accumulate_price(timestamp)?;
+    bonding_curve.set_reserves(&quote.reserves_after);
+    bonding_curve.record_trade()?;
+
+    let events = track_market_cap(
+        &mut bonding_curve,
+        &mut ctx.accounts.token_metadata,
+        &mut ctx.accounts.king_of_the_hill,
+        ctx.bumps.king_of_the_hill,
+        &ctx.accounts.global_state,
+        sol_usd_price,
+        timestamp,
+    )?;
+
+    record_price(&ctx.accounts.price_history, &bonding_curve, quote.sol_amount, timestamp)?;
+    drop(bonding_curve);
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.ensure_initialized(seller, ctx.bumps.user_stats);
+    user_stats.update_sell_stats(token_amount, quote.net_sol_amount, quote.fee_amount, timestamp)?;
+
+    let position = &mut ctx.accounts.position;
+    position.ensure_initialized(seller, mint, ctx.bumps.position, timestamp);
+    position.record_sell(token_amount, quote.net_sol_amount, timestamp)?;
 
-#[event]
-pub struct TokenSellEvent {
-    pub seller: Pubkey,
-    pub token_launch: Pubkey,
-    pub token_amount: u64,
-    pub sol_amount: u64,
-    pub fee_amount: u64,
-    pub new_supply: u64,
-    pub timestamp: i64,
+    emit_event!(ctx, TradeEvent {
+        version: EVENT_VERSION,
+        mint,
+        user: seller,
+        is_buy: false,
+        sol_amount: quote.sol_amount,
+        token_amount,
+        platform_fee: quote.fee_amount,
+        reserves_before,
+        reserves_after: quote.reserves_after,
+        slot: Clock::get()?.slot,
+        timestamp,
+    });
+
+    if let Some(event) = events.king_changed {
+        emit_event!(ctx, event);
+    }
+    if let Some(event) = events.completed {
+        emit_event!(ctx, event);
+    }
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{FeeRecipientUpdated, EVENT_VERSION},
+    state::GlobalState,
+};
+
+#[derive(Accounts)]
+pub struct SetFeeRecipient<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<SetFeeRecipient>, fee_recipient: Pubkey) -> Result<()> {
+    require!(
+        fee_recipient != Pubkey::default(),
+        PumpCloneError::InvalidFeeRecipient
+    );
+
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_fee_recipient = global_state.fee_recipient;
+    global_state.fee_recipient = fee_recipient;
+
+    emit!(FeeRecipientUpdated {
+        version: EVENT_VERSION,
+        previous_fee_recipient,
+        fee_recipient,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, events::{GuardianUpdated, EVENT_VERSION}, state::GlobalState};
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_guardian = global_state.guardian;
+    global_state.guardian = guardian;
+
+    emit!(GuardianUpdated {
+        version: EVENT_VERSION,
+        previous_guardian,
+        guardian,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
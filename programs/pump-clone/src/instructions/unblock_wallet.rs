@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{WalletUnblocked, EVENT_VERSION},
+    state::{BlockedWallet, GlobalState},
+};
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct UnblockWallet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"blocked_wallet", wallet.as_ref()],
+        bump = blocked_wallet.bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+}
+
+pub fn handler(ctx: Context<UnblockWallet>, wallet: Pubkey) -> Result<()> {
+    emit!(WalletUnblocked {
+        version: EVENT_VERSION,
+        wallet,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::PumpCloneError, events::{PauseFlagsUpdated, EVENT_VERSION}, state::GlobalState};
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<Unpause>, scopes: u8) -> Result<()> {
+    require!(
+        scopes != 0 && scopes & !GlobalState::PAUSE_ALL == 0,
+        PumpCloneError::InvalidConfigurationParams
+    );
+
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_flags = global_state.pause_flags;
+    global_state.pause_flags &= !scopes;
+
+    emit!(PauseFlagsUpdated {
+        version: EVENT_VERSION,
+        previous_flags,
+        pause_flags: global_state.pause_flags,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
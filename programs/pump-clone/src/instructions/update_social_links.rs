@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::PumpCloneError,
+    events::{MetadataUpdated, EVENT_VERSION},
+    state::{BondingCurve, TokenMetadata},
+};
+
+/// Creator-only. Links are frozen once the curve graduates so holders of a
+/// migrated token can rely on them.
+#[derive(Accounts)]
+pub struct UpdateSocialLinks<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata", token_metadata.mint.as_ref()],
+        bump = token_metadata.bump,
+        has_one = creator @ PumpCloneError::UnauthorizedCreator
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        seeds = [b"bonding_curve", token_metadata.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+        constraint = !bonding_curve.load()?.is_complete() @ PumpCloneError::TokenAlreadyGraduated
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SocialLinksParams {
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+pub fn handler(ctx: Context<UpdateSocialLinks>, params: SocialLinksParams) -> Result<()> {
+    let token_metadata = &mut ctx.accounts.token_metadata;
+
+    if let Some(twitter) = params.twitter {
+        token_metadata.twitter = twitter;
+    }
+
+    if let Some(telegram) = params.telegram {
+        token_metadata.telegram = telegram;
+    }
+
+    if let Some(website) = params.website {
+        token_metadata.website = website;
+    }
+
+    token_metadata.validate_metadata()?;
+
+    emit!(MetadataUpdated {
+        version: EVENT_VERSION,
+        mint: token_metadata.mint,
+        creator: token_metadata.creator,
+        twitter: token_metadata.twitter.clone(),
+        telegram: token_metadata.telegram.clone(),
+        website: token_metadata.website.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
\ No newline at end of file
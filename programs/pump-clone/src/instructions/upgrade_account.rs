@@ -0,0 +1,187 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    error::PumpCloneError,
+    events::{AccountUpgraded, EVENT_VERSION},
+    state::{
+        BondingCurve, BondingCurveV0, Comment, GlobalState, LegacyBondingCurve, Position,
+        TokenMetadata, UserStats, Versioned, VersionedAccount,
+    },
+};
+
+#[derive(Accounts)]
+pub struct UpgradeAccount<'info> {
+    /// Pays for the extra rent when the account grows
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: read raw so that a pre-versioning `GlobalState` can upgrade
+    /// itself; the authority is checked at its fixed offset in the handler
+    #[account(
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: UncheckedAccount<'info>,
+
+    /// CHECK: owner is checked here, the discriminator against the requested
+    /// kind in the handler
+    #[account(mut, owner = crate::ID)]
+    pub target: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpgradeAccount>, kind: VersionedAccount) -> Result<()> {
+    {
+        let data = ctx.accounts.global_state.try_borrow_data()?;
+        let authority = data
+            .get(8..40)
+            .ok_or(PumpCloneError::InvalidAccount)?;
+        require!(
+            authority == ctx.accounts.authority.key().as_ref(),
+            PumpCloneError::UnauthorizedAdmin
+        );
+    }
+
+    let (from_version, to_version) = upgrade_target(
+        kind,
+        &ctx.accounts.target,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(AccountUpgraded {
+        version: EVENT_VERSION,
+        account: ctx.accounts.target.key(),
+        kind,
+        from_version,
+        to_version,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Brings `target` up to the current layout of `kind`, with `payer` covering
+/// any extra rent. Returns the account's version before and after. Shared
+/// with the admin action executor so the multisig and governance can upgrade
+/// accounts too.
+pub fn upgrade_target<'info>(
+    kind: VersionedAccount,
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<(u8, u8)> {
+    require_keys_eq!(*target.owner, crate::ID, PumpCloneError::InvalidAccount);
+
+    match kind {
+        VersionedAccount::GlobalState => upgrade::<GlobalState>(target, payer, system_program),
+        VersionedAccount::BondingCurve => upgrade_bonding_curve(target, payer, system_program),
+        VersionedAccount::TokenMetadata => upgrade::<TokenMetadata>(target, payer, system_program),
+        VersionedAccount::Comment => upgrade::<Comment>(target, payer, system_program),
+        VersionedAccount::UserStats => upgrade::<UserStats>(target, payer, system_program),
+        VersionedAccount::Position => upgrade::<Position>(target, payer, system_program),
+    }
+}
+
+/// Grows `target` to the current layout, tops up its rent from the
+/// payer and rewrites it at the current version.
+fn upgrade<'info, T: Versioned>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<(u8, u8)> {
+    require!(
+        target.try_borrow_data()?.get(..8) == Some(&T::DISCRIMINATOR[..]),
+        PumpCloneError::InvalidAccount
+    );
+
+    if target.data_len() < T::SPACE {
+        resize(target, payer, system_program, T::SPACE)?;
+    }
+
+    let mut account = T::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+    let from_version = account.version();
+    require!(
+        from_version < T::CURRENT_VERSION,
+        PumpCloneError::AccountAlreadyUpgraded
+    );
+
+    account.migrate();
+    account.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+
+    Ok((from_version, T::CURRENT_VERSION))
+}
+
+/// Steps a Borsh-encoded curve through each version up to the zero-copy
+/// layout: version 0 is stamped as version 1, and version 1 is rewritten as
+/// version 2. The discriminator is unchanged, so the account keeps its
+/// address and type; only its size and encoding change.
+fn upgrade_bonding_curve<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<(u8, u8)> {
+    require!(
+        target.try_borrow_data()?.get(..8) == Some(&BondingCurve::DISCRIMINATOR[..]),
+        PumpCloneError::InvalidAccount
+    );
+    require!(
+        target.data_len() != BondingCurve::LEN,
+        PumpCloneError::AccountAlreadyUpgraded
+    );
+
+    let (from_version, legacy) = {
+        let data = target.try_borrow_data()?;
+        if data.len() == BondingCurveV0::LEN {
+            (
+                BondingCurveV0::VERSION,
+                BondingCurveV0::try_from_account_data(&data)?.into_v1(),
+            )
+        } else {
+            let legacy = LegacyBondingCurve::try_from_account_data(&data)?;
+            (legacy.version, legacy)
+        }
+    };
+
+    resize(target, payer, system_program, BondingCurve::LEN)?;
+
+    let curve = legacy.into_zero_copy();
+    target.try_borrow_mut_data()?[8..BondingCurve::LEN].copy_from_slice(bytemuck::bytes_of(&curve));
+
+    Ok((from_version, BondingCurve::VERSION))
+}
+
+/// Reallocs `target` to `space` bytes, topping its rent up from the payer
+/// first when it grows.
+fn resize<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(target.lamports());
+    if rent_due > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+
+    target.realloc(space, true)?;
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    constants::BONDING_CURVE_SEED,
+    error::PumpCloneError,
+    events::{emit_event, BondingCurveMigrated, EVENT_VERSION},
+    state::{BondingCurve, GlobalState},
+};
+
+/// Pulls a completed curve's liquidity out for migration: the remaining
+/// tokens and the SOL reserves go to the authority, less the migration fee,
+/// which is paid into the fee vault. Emptying the real reserves leaves the
+/// curve closable through `close_token`.
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PumpCloneError::UnauthorizedAdmin,
+        constraint = !global_state.is_paused(GlobalState::PAUSE_WITHDRAW) @ PumpCloneError::EmergencyPauseActivated
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = mint
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Withdraw>) -> Result<()> {
+    let event = migrate(
+        &ctx.accounts.global_state,
+        &ctx.accounts.bonding_curve.to_account_info(),
+        &ctx.accounts.bonding_curve_token_account.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.authority_token_account.to_account_info(),
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    emit_event!(ctx, event);
+
+    Ok(())
+}
+
+/// Moves a completed curve's tokens and SOL reserves to `recipient`, less
+/// the migration fee. Shared with the admin executor, which checks the
+/// accounts it was handed before calling this.
+pub fn migrate<'info>(
+    global_state: &GlobalState,
+    bonding_curve: &AccountInfo<'info>,
+    bonding_curve_token_account: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    recipient_token_account: &AccountInfo<'info>,
+    fee_vault: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<BondingCurveMigrated> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    // The curve signs the token transfer, so it can't stay borrowed across
+    // the CPI
+    let (mint, curve_bump, sol_amount) = {
+        let data = bonding_curve.try_borrow_data()?;
+        let curve: &BondingCurve = bytemuck::from_bytes(&data[8..BondingCurve::LEN]);
+        require!(curve.is_complete(), PumpCloneError::CurveNotComplete);
+        curve.require_not_refunding(timestamp)?;
+        (curve.mint, curve.bump, curve.real_sol_reserves)
+    };
+
+    let migration_fee = global_state.migration_fee.min(sol_amount);
+    let token_amount = token::accessor::amount(bonding_curve_token_account)?;
+
+    let seeds = &[BONDING_CURVE_SEED, mint.as_ref(), &[curve_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    if token_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: bonding_curve_token_account.clone(),
+                    to: recipient_token_account.clone(),
+                    authority: bonding_curve.clone(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+        )?;
+    }
+
+    // The curve is owned by this program, so its reserves are paid out by
+    // moving lamports directly
+    **bonding_curve.try_borrow_mut_lamports()? -= sol_amount;
+    **recipient.try_borrow_mut_lamports()? += sol_amount - migration_fee;
+    **fee_vault.try_borrow_mut_lamports()? += migration_fee;
+
+    let mut data = bonding_curve.try_borrow_mut_data()?;
+    let curve: &mut BondingCurve = bytemuck::from_bytes_mut(&mut data[8..BondingCurve::LEN]);
+    curve.real_sol_reserves = 0;
+    curve.real_token_reserves = 0;
+
+    Ok(BondingCurveMigrated {
+        version: EVENT_VERSION,
+        mint,
+        pool: recipient.key(),
+        sol_amount,
+        token_amount,
+        migration_fee,
+        timestamp,
+    })
+}
\ No newline at end of file
@@ -1,8 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use anchor_spl::associated_token::AssociatedToken;
 
-declare_id!("PumpCLoneProgram11111111111111111111111111");
+pub mod admin;
+pub mod constants;
+pub mod curve;
+pub mod error;
+pub mod events;
+pub mod governance;
+pub mod instructions;
+pub mod oracle;
+pub mod state;
+pub mod utils;
+
+use governance::*;
+use instructions::*;
+use state::{AdminAction, ConfigUpdate, VersionedAccount};
+
+declare_id!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
 
 #[program]
 pub mod pump_clone {
@@ -10,365 +23,203 @@ pub mod pump_clone {
 
     pub fn initialize_platform(
         ctx: Context<InitializePlatform>,
-        fee_basis_points: u16,
-        initial_virtual_token_reserves: u64,
-        initial_virtual_sol_reserves: u64,
-    ) -> Result<()> {
-        require!(fee_basis_points <= 1000, PumpError::InvalidFeeRate);
-        require!(initial_virtual_token_reserves > 0, PumpError::InvalidReserves);
-        require!(initial_virtual_sol_reserves > 0, PumpError::InvalidReserves);
-
-        let platform_config = &mut ctx.accounts.platform_config;
-        platform_config.authority = ctx.accounts.authority.key();
-        platform_config.fee_basis_points = fee_basis_points;
-        platform_config.initial_virtual_token_reserves = initial_virtual_token_reserves;
-        platform_config.initial_virtual_sol_reserves = initial_virtual_sol_reserves;
-        platform_config.total_tokens_created = 0;
-        platform_config.total_volume = 0;
-        platform_config.is_paused = false;
-
-        Ok(())
-    }
-
-    pub fn create_token(
-        ctx: Context<CreateToken>,
-        name: String,
-        symbol: String,
-        uri: String,
-        initial_supply: u64,
+        params: InitializePlatformParams,
     ) -> Result<()> {
-        require!(!ctx.accounts.platform_config.is_paused, PumpError::PlatformPaused);
-        require!(name.len() <= 32, PumpError::NameTooLong);
-        require!(symbol.len() <= 10, PumpError::SymbolTooLong);
-        require!(uri.len() <= 200, PumpError::UriTooLong);
-        require!(initial_supply > 0, PumpError::InvalidSupply);
-
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let platform_config = &mut ctx.accounts.platform_config;
-
-        bonding_curve.mint = ctx.accounts.mint.key();
-        bonding_curve.creator = ctx.accounts.creator.key();
-        bonding_curve.name = name;
-        bonding_curve.symbol = symbol;
-        bonding_curve.uri = uri;
-        bonding_curve.virtual_token_reserves = platform_config.initial_virtual_token_reserves;
-        bonding_curve.virtual_sol_reserves = platform_config.initial_virtual_sol_reserves;
-        bonding_curve.real_token_reserves = initial_supply;
-        bonding_curve.real_sol_reserves = 0;
-        bonding_curve.total_supply = initial_supply;
-        bonding_curve.is_complete = false;
-        bonding_curve.created_at = Clock::get()?.unix_timestamp;
-        bonding_curve.bump = ctx.bumps.bonding_curve;
-
-        platform_config.total_tokens_created = platform_config.total_tokens_created.checked_add(1).unwrap();
-
-        token::mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.bonding_curve_token_account.to_account_info(),
-                    authority: ctx.accounts.bonding_curve.to_account_info(),
-                },
-                &[&[
-                    b"bonding_curve",
-                    ctx.accounts.mint.key().as_ref(),
-                    &[bonding_curve.bump],
-                ]],
-            ),
-            initial_supply,
-        )?;
-
-        emit!(TokenCreated {
-            mint: ctx.accounts.mint.key(),
-            creator: ctx.accounts.creator.key(),
-            name: bonding_curve.name.clone(),
-            symbol: bonding_curve.symbol.clone(),
-            uri: bonding_curve.uri.clone(),
-            initial_supply,
-            timestamp: bonding_curve.created_at,
-        });
-
-        Ok(())
+        instructions::initialize_platform::handler(ctx, params)
+    }
+
+    pub fn create_token(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
+        instructions::create_token::handler(ctx, params)
     }
 
     pub fn buy_tokens(
         ctx: Context<BuyTokens>,
         sol_amount: u64,
         min_tokens_out: u64,
+        allowlist_proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        require!(!ctx.accounts.platform_config.is_paused, PumpError::PlatformPaused);
-        require!(sol_amount > 0, PumpError::InvalidAmount);
-        require!(!ctx.accounts.bonding_curve.is_complete, PumpError::BondingCurveComplete);
-
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let platform_config = &ctx.accounts.platform_config;
-
-        let fee_amount = sol_amount
-            .checked_mul(platform_config.fee_basis_points as u64)
-            .unwrap()
-            .checked_div(10000)
-            .unwrap();
-        let sol_amount_after_fee = sol_amount.checked_sub(fee_amount).unwrap();
-
-        let tokens_out = calculate_tokens_out(
-            sol_amount_after_fee,
-            bonding_curve.virtual_sol_reserves,
-            bonding_curve.virtual_token_reserves,
-        )?;
-
-        require!(tokens_out >= min_tokens_out, PumpError::SlippageExceeded);
-        require!(tokens_out <= bonding_curve.real_token_reserves, PumpError::InsufficientTokens);
-
-        bonding_curve.virtual_sol_reserves = bonding_curve.virtual_sol_reserves
-            .checked_add(sol_amount_after_fee)
-            .unwrap();
-        bonding_curve.virtual_token_reserves = bonding_curve.virtual_token_reserves
-            .checked_sub(tokens_out)
-            .unwrap();
-        bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
-            .checked_sub(tokens_out)
-            .unwrap();
-        bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
-            .checked_add(sol_amount_after_fee)
-            .unwrap();
-
-        let platform_config = &mut ctx.accounts.platform_config;
-        platform_config.total_volume = platform_config.total_volume
-            .checked_add(sol_amount)
-            .unwrap();
-
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.bonding_curve.to_account_info(),
-                },
-            ),
-            sol_amount_after_fee,
-        )?;
-
-        if fee_amount > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.buyer.to_account_info(),
-                        to: ctx.accounts.platform_config.to_account_info(),
-                    },
-                ),
-                fee_amount,
-            )?;
-        }
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.bonding_curve_token_account.to_account_info(),
-                    to: ctx.accounts.buyer_token_account.to_account_info(),
-                    authority: ctx.accounts.bonding_curve.to_account_info(),
-                },
-                &[&[
-                    b"bonding_curve",
-                    ctx.accounts.mint.key().as_ref(),
-                    &[bonding_curve.bump],
-                ]],
-            ),
-            tokens_out,
-        )?;
-
-        emit!(TokensPurchased {
-            mint: ctx.accounts.mint.key(),
-            buyer: ctx.accounts.buyer.key(),
-            sol_amount,
-            tokens_out,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
-    }
-
-    pub fn sell_tokens(
-        ctx: Context<SellTokens>,
+        instructions::buy_tokens::handler(ctx, sol_amount, min_tokens_out, allowlist_proof)
+    }
+
+    pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64, min_sol_out: u64) -> Result<()> {
+        instructions::sell_tokens::handler(ctx, token_amount, min_sol_out)
+    }
+
+    pub fn complete_bonding_curve(ctx: Context<CompleteBondingCurve>) -> Result<()> {
+        instructions::complete_bonding_curve::handler(ctx)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        instructions::withdraw::handler(ctx)
+    }
+
+    pub fn block_wallet(ctx: Context<BlockWallet>, wallet: Pubkey, reason: String) -> Result<()> {
+        instructions::block_wallet::handler(ctx, wallet, reason)
+    }
+
+    pub fn unblock_wallet(ctx: Context<UnblockWallet>, wallet: Pubkey) -> Result<()> {
+        instructions::unblock_wallet::handler(ctx, wallet)
+    }
+
+    pub fn clear_allowlist(ctx: Context<ClearAllowlist>) -> Result<()> {
+        instructions::clear_allowlist::handler(ctx)
+    }
+
+    pub fn presale_buy(
+        ctx: Context<PresaleBuy>,
         token_amount: u64,
-        min_sol_out: u64,
+        max_sol_cost: u64,
+        allocation: u64,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        require!(!ctx.accounts.platform_config.is_paused, PumpError::PlatformPaused);
-        require!(token_amount > 0, PumpError::InvalidAmount);
-        require!(!ctx.accounts.bonding_curve.is_complete, PumpError::BondingCurveComplete);
-
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let platform_config = &ctx.accounts.platform_config;
-
-        let sol_out = calculate_sol_out(
-            token_amount,
-            bonding_curve.virtual_token_reserves,
-            bonding_curve.virtual_sol_reserves,
-        )?;
-
-        let fee_amount = sol_out
-            .checked_mul(platform_config.fee_basis_points as u64)
-            .unwrap()
-            .checked_div(10000)
-            .unwrap();
-        let sol_out_after_fee = sol_out.checked_sub(fee_amount).unwrap();
-
-        require!(sol_out_after_fee >= min_sol_out, PumpError::SlippageExceeded);
-        require!(sol_out <= bonding_curve.real_sol_reserves, PumpError::InsufficientSol);
-
-        bonding_curve.virtual_token_reserves = bonding_curve.virtual_token_reserves
-            .checked_add(token_amount)
-            .unwrap();
-        bonding_curve.virtual_sol_reserves = bonding_curve.virtual_sol_reserves
-            .checked_sub(sol_out)
-            .unwrap();
-        bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
-            .checked_add(token_amount)
-            .unwrap();
-        bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
-            .checked_sub(sol_out)
-            .unwrap();
-
-        let platform_config = &mut ctx.accounts.platform_config;
-        platform_config.total_volume = platform_config.total_volume
-            .checked_add(sol_out)
-            .unwrap();
-
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.seller_token_account.to_account_info(),
-                    to: ctx.accounts.bonding_curve_token_account.to_account_info(),
-                    authority: ctx.accounts.seller.to_account_info(),
-                },
-            ),
-            token_amount,
-        )?;
-
-        **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= sol_out_after_fee;
-        **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += sol_out_after_fee;
-
-        if fee_amount > 0 {
-            **ctx.accounts.bonding_curve.to_account_info().try_borrow_mut_lamports()? -= fee_amount;
-            **ctx.accounts.platform_config.to_account_info().try_borrow_mut_lamports()? += fee_amount;
-        }
-
-        emit!(TokensSold {
-            mint: ctx.accounts.mint.key(),
-            seller: ctx.accounts.seller.key(),
-            token_amount,
-            sol_out: sol_out_after_fee,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
+        instructions::presale_buy::handler(ctx, token_amount, max_sol_cost, allocation, proof)
     }
 
-    pub fn complete_bonding_curve(ctx: Context<CompleteBondingCurve>) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        
-        require!(!bonding_curve.is_complete, PumpError::BondingCurveComplete);
-        require!(
-            bonding_curve.virtual_sol_reserves >= 85_000_000_000,
-            PumpError::InsufficientLiquidity
-        );
+    pub fn finalize_presale(ctx: Context<FinalizePresale>) -> Result<()> {
+        instructions::finalize_presale::handler(ctx)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        instructions::claim_vested::handler(ctx)
+    }
+
+    pub fn pause(ctx: Context<Pause>, scopes: u8) -> Result<()> {
+        instructions::pause::handler(ctx, scopes)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, scopes: u8) -> Result<()> {
+        instructions::unpause::handler(ctx, scopes)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>, fee_recipient: Pubkey) -> Result<()> {
+        instructions::set_fee_recipient::handler(ctx, fee_recipient)
+    }
+
+    pub fn create_admin_multisig(
+        ctx: Context<CreateAdminMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_admin_multisig::handler(ctx, signers, threshold)
+    }
+
+    pub fn create_admin_proposal(ctx: Context<CreateAdminProposal>, action: AdminAction) -> Result<()> {
+        instructions::create_admin_proposal::handler(ctx, action)
+    }
+
+    pub fn approve_admin_proposal(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+        instructions::approve_admin_proposal::handler(ctx)
+    }
 
-        bonding_curve.is_complete = true;
+    pub fn execute_admin_proposal(ctx: Context<ExecuteAdminProposal>) -> Result<()> {
+        instructions::execute_admin_proposal::handler(ctx)
+    }
+
+    pub fn queue_config_change(ctx: Context<QueueConfigChange>, update: ConfigUpdate) -> Result<()> {
+        instructions::queue_config_change::handler(ctx, update)
+    }
 
-        emit!(BondingCurveCompleted {
-            mint: ctx.accounts.mint.key(),
-            final_sol_reserves: bonding_curve.real_sol_reserves,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::execute_config_change::handler(ctx)
+    }
 
-        Ok(())
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::cancel_config_change::handler(ctx)
     }
 
-    pub fn update_platform_config(
-        ctx: Context<UpdatePlatformConfig>,
-        fee_basis_points: Option<u16>,
-        is_paused: Option<bool>,
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        params: GovernanceParams,
     ) -> Result<()> {
-        let platform_config = &mut ctx.accounts.platform_config;
-
-        if let Some(fee) = fee_basis_points {
-            require!(fee <= 1000, PumpError::InvalidFeeRate);
-            platform_config.fee_basis_points = fee;
-        }
-
-        if let Some(paused) = is_paused {
-            platform_config.is_paused = paused;
-        }
-
-        Ok(())
-    }
-
-    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
-        let platform_config = &ctx.accounts.platform_config;
-        
-        require!(
-            amount <= platform_config.to_account_info().lamports(),
-            PumpError::InsufficientFunds
-        );
-
-        **platform_config.to_account_info().try_borrow_mut_lamports()? -= amount;
-        **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
-
-        Ok(())
-    }
-}
-
-fn calculate_tokens_out(
-    sol_in: u64,
-    virtual_sol_reserves: u64,
-    virtual_token_reserves: u64,
-) -> Result<u64> {
-    let numerator = sol_in
-        .checked_mul(virtual_token_reserves)
-        .ok_or(PumpError::MathOverflow)?;
-    let denominator = virtual_sol_reserves
-        .checked_add(sol_in)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    numerator
-        .checked_div(denominator)
-        .ok_or(PumpError::MathOverflow.into())
-}
-
-fn calculate_sol_out(
-    tokens_in: u64,
-    virtual_token_reserves: u64,
-    virtual_sol_reserves: u64,
-) -> Result<u64> {
-    let numerator = tokens_in
-        .checked_mul(virtual_sol_reserves)
-        .ok_or(PumpError::MathOverflow)?;
-    let denominator = virtual_token_reserves
-        .checked_add(tokens_in)
-        .ok_or(PumpError::MathOverflow)?;
-    
-    numerator
-        .checked_div(denominator)
-        .ok_or(PumpError::MathOverflow.into())
-}
-
-#[derive(Accounts)]
-pub struct InitializePlatform<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + PlatformConfig::INIT_SPACE,
-        seeds = [b"platform_config"],
-        bump
-    )]
-    pub platform_config: Account<'info, PlatformConfig>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct
\ No newline at end of file
+        governance::initialize_governance::handler(ctx, params)
+    }
+
+    pub fn accept_governance_authority(ctx: Context<AcceptGovernanceAuthority>) -> Result<()> {
+        governance::accept_governance_authority::handler(ctx)
+    }
+
+    pub fn deposit_votes(ctx: Context<DepositVotes>, amount: u64) -> Result<()> {
+        governance::deposit_votes::handler(ctx, amount)
+    }
+
+    pub fn withdraw_votes(ctx: Context<WithdrawVotes>, amount: u64) -> Result<()> {
+        governance::withdraw_votes::handler(ctx, amount)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        governance::create_proposal::handler(ctx, action)
+    }
+
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        governance::cast_vote::handler(ctx, support)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        governance::execute_proposal::handler(ctx)
+    }
+
+    pub fn post_comment(ctx: Context<PostComment>, comment_id: u64, content: String) -> Result<()> {
+        instructions::post_comment::handler(ctx, comment_id, content)
+    }
+
+    pub fn reply_comment(ctx: Context<ReplyComment>, comment_id: u64, content: String) -> Result<()> {
+        instructions::reply_comment::handler(ctx, comment_id, content)
+    }
+
+    pub fn like_comment(ctx: Context<LikeComment>) -> Result<()> {
+        instructions::like_comment::handler(ctx)
+    }
+
+    pub fn delete_comment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeleteComment<'info>>,
+    ) -> Result<()> {
+        instructions::delete_comment::handler(ctx)
+    }
+
+    pub fn update_social_links(
+        ctx: Context<UpdateSocialLinks>,
+        params: SocialLinksParams,
+    ) -> Result<()> {
+        instructions::update_social_links::handler(ctx, params)
+    }
+
+    pub fn quote_buy(ctx: Context<CurveView>, sol_amount: u64) -> Result<()> {
+        instructions::quote_buy::handler(ctx, sol_amount)
+    }
+
+    pub fn quote_sell(ctx: Context<CurveView>, token_amount: u64) -> Result<()> {
+        instructions::quote_sell::handler(ctx, token_amount)
+    }
+
+    pub fn quote_buy_exact_out(ctx: Context<CurveView>, token_amount: u64) -> Result<()> {
+        instructions::quote_buy_exact_out::handler(ctx, token_amount)
+    }
+
+    pub fn get_curve_state(ctx: Context<CurveView>) -> Result<()> {
+        instructions::get_curve_state::handler(ctx)
+    }
+
+    pub fn close_token(ctx: Context<CloseToken>) -> Result<()> {
+        instructions::close_token::handler(ctx)
+    }
+
+    pub fn refund(ctx: Context<Refund>, token_amount: u64) -> Result<()> {
+        instructions::refund::handler(ctx, token_amount)
+    }
+
+    pub fn upgrade_account(ctx: Context<UpgradeAccount>, kind: VersionedAccount) -> Result<()> {
+        instructions::upgrade_account::handler(ctx, kind)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::{AggregatorAccountData, SwitchboardDecimal, SWITCHBOARD_PROGRAM_ID};
+
+use crate::{error::PumpCloneError, state::GlobalState};
+
+/// Fixed-point scale of USD amounts: oracle prices and USD thresholds in
+/// `GlobalState` are expressed in micro-dollars.
+pub const USD_SCALE: u64 = 1_000_000;
+
+const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
+
+/// A source for the SOL/USD price used to express thresholds in USD.
+pub trait Oracle {
+    /// Price of one SOL in USD scaled by `USD_SCALE`, rejected when it is
+    /// older than `max_staleness` seconds or its confidence interval is wider
+    /// than `max_confidence_bps` of the price.
+    fn sol_usd_price(&self, now: i64, max_staleness: i64, max_confidence_bps: u16) -> Result<u64>;
+}
+
+pub struct SwitchboardOracle<'a, 'info> {
+    feed: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> SwitchboardOracle<'a, 'info> {
+    pub fn new(feed: &'a AccountInfo<'info>) -> Result<Self> {
+        require_keys_eq!(*feed.owner, *SWITCHBOARD_PROGRAM_ID, PumpCloneError::InvalidPriceFeed);
+
+        Ok(Self { feed })
+    }
+}
+
+impl Oracle for SwitchboardOracle<'_, '_> {
+    fn sol_usd_price(&self, now: i64, max_staleness: i64, max_confidence_bps: u16) -> Result<u64> {
+        let data = self.feed.try_borrow_data()?;
+        let aggregator = AggregatorAccountData::new_from_bytes(&data)
+            .map_err(|_| error!(PumpCloneError::InvalidPriceFeed))?;
+        let result = aggregator
+            .get_result()
+            .map_err(|_| error!(PumpCloneError::OraclePriceFeedUnavailable))?;
+        // The aggregator is packed, so the round is copied out before its
+        // fields are borrowed
+        let round = aggregator.latest_confirmed_round;
+        let std_deviation = round.std_deviation;
+        let round_open_timestamp = round.round_open_timestamp;
+
+        validate_price(
+            decimal_to_usd(&result)?,
+            decimal_to_usd(&std_deviation)?,
+            round_open_timestamp,
+            now,
+            max_staleness,
+            max_confidence_bps,
+        )
+    }
+}
+
+/// Test-only price feed: a program-owned account whose price is written
+/// directly by the test harness.
+#[cfg(feature = "mock-oracle")]
+#[account]
+pub struct MockPriceFeed {
+    pub price: u64,
+    pub confidence: u64,
+    pub updated_at: i64,
+}
+
+#[cfg(feature = "mock-oracle")]
+pub struct MockOracle {
+    feed: MockPriceFeed,
+}
+
+#[cfg(feature = "mock-oracle")]
+impl MockOracle {
+    pub fn new(feed: &AccountInfo) -> Result<Self> {
+        let feed = crate::utils::load_account::<MockPriceFeed>(feed)
+            .map_err(|_| error!(PumpCloneError::InvalidPriceFeed))?;
+
+        Ok(Self { feed })
+    }
+}
+
+#[cfg(feature = "mock-oracle")]
+impl Oracle for MockOracle {
+    fn sol_usd_price(&self, now: i64, max_staleness: i64, max_confidence_bps: u16) -> Result<u64> {
+        validate_price(
+            self.feed.price,
+            self.feed.confidence,
+            self.feed.updated_at,
+            now,
+            max_staleness,
+            max_confidence_bps,
+        )
+    }
+}
+
+/// Resolves the configured feed to an `Oracle`. With the `mock-oracle`
+/// feature, feeds owned by this program are read as `MockPriceFeed`s.
+pub fn load_oracle<'a, 'info>(feed: &'a AccountInfo<'info>) -> Result<Box<dyn Oracle + 'a>> {
+    #[cfg(feature = "mock-oracle")]
+    if feed.owner == &crate::ID {
+        return Ok(Box::new(MockOracle::new(feed)?));
+    }
+
+    Ok(Box::new(SwitchboardOracle::new(feed)?))
+}
+
+/// Reads the SOL/USD price from `feed` using the limits in `global_state`.
+pub fn sol_usd_price(feed: &AccountInfo, global_state: &GlobalState) -> Result<u64> {
+    require_keys_eq!(feed.key(), global_state.oracle_feed, PumpCloneError::InvalidPriceFeed);
+
+    load_oracle(feed)?.sol_usd_price(
+        Clock::get()?.unix_timestamp,
+        global_state.oracle_max_staleness,
+        global_state.oracle_max_confidence_bps,
+    )
+}
+
+/// Converts a lamport amount to USD (scaled by `USD_SCALE`).
+pub fn lamports_to_usd(lamports: u64, sol_usd_price: u64) -> Result<u64> {
+    let usd = (lamports as u128)
+        .checked_mul(sol_usd_price as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?
+        / LAMPORTS_PER_SOL;
+
+    u64::try_from(usd).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+}
+
+fn validate_price(
+    price: u64,
+    confidence: u64,
+    updated_at: i64,
+    now: i64,
+    max_staleness: i64,
+    max_confidence_bps: u16,
+) -> Result<u64> {
+    require!(price > 0, PumpCloneError::OraclePriceFeedUnavailable);
+    require!(
+        now.saturating_sub(updated_at) <= max_staleness,
+        PumpCloneError::PriceDataStale
+    );
+    require!(
+        (confidence as u128) * 10000 <= (price as u128) * (max_confidence_bps as u128),
+        PumpCloneError::OracleConfidenceTooWide
+    );
+
+    Ok(price)
+}
+
+fn decimal_to_usd(value: &SwitchboardDecimal) -> Result<u64> {
+    let mantissa = u128::try_from(value.mantissa)
+        .map_err(|_| error!(PumpCloneError::InvalidPriceFeed))?;
+    let divisor = 10u128
+        .checked_pow(value.scale)
+        .ok_or(PumpCloneError::InvalidPriceFeed)?;
+    let usd = mantissa
+        .checked_mul(USD_SCALE as u128)
+        .ok_or(PumpCloneError::ArithmeticOverflow)?
+        / divisor;
+
+    u64::try_from(usd).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+}
\ No newline at end of file
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::curve::{self, CurveReserves};
+use crate::error::PumpCloneError;
+
 #[account]
 pub struct GlobalState {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub guardian: Pubkey,
     pub fee_recipient: Pubkey,
     pub platform_fee_bps: u16,
     pub creator_fee_bps: u16,
@@ -12,13 +17,26 @@ pub struct GlobalState {
     pub initial_virtual_token_reserves: u64,
     pub initial_virtual_sol_reserves: u64,
     pub initial_real_token_reserves: u64,
-    pub paused: bool,
+    pub max_creator_allocation_bps: u16,
+    pub graduation_market_cap_usd: u64,
+    pub king_of_hill_bps: u16,
+    pub oracle_feed: Pubkey,
+    pub oracle_max_staleness: i64,
+    pub oracle_max_confidence_bps: u16,
+    pub token_expiry: i64,
+    pub config_timelock: i64,
+    pub config_change_count: u64,
+    pub pause_flags: u8,
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; 64],
 }
 
 impl GlobalState {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        1 + 32 + // pending_authority (Option<Pubkey>)
+        32 + // guardian
         32 + // fee_recipient
         2 + // platform_fee_bps
         2 + // creator_fee_bps
@@ -28,111 +46,378 @@ impl GlobalState {
         8 + // initial_virtual_token_reserves
         8 + // initial_virtual_sol_reserves
         8 + // initial_real_token_reserves
-        1 + // paused
-        1; // bump
+        2 + // max_creator_allocation_bps
+        8 + // graduation_market_cap_usd
+        2 + // king_of_hill_bps
+        32 + // oracle_feed
+        8 + // oracle_max_staleness
+        2 + // oracle_max_confidence_bps
+        8 + // token_expiry
+        8 + // config_timelock
+        8 + // config_change_count
+        1 + // pause_flags
+        1 + // bump
+        1 + // version
+        64; // reserved
+
+    pub const VERSION: u8 = 1;
+
+    pub const PAUSE_CREATE: u8 = 1 << 0;
+    pub const PAUSE_BUY: u8 = 1 << 1;
+    pub const PAUSE_SELL: u8 = 1 << 2;
+    pub const PAUSE_MIGRATE: u8 = 1 << 3;
+    pub const PAUSE_WITHDRAW: u8 = 1 << 4;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_CREATE
+        | Self::PAUSE_BUY
+        | Self::PAUSE_SELL
+        | Self::PAUSE_MIGRATE
+        | Self::PAUSE_WITHDRAW;
+
+    pub fn is_paused(&self, scope: u8) -> bool {
+        self.pause_flags & scope != 0
+    }
+
+    /// USD market cap a token must reach to compete for king of the hill, as
+    /// a fraction of the graduation market cap.
+    pub fn king_of_hill_threshold(&self) -> u64 {
+        ((self.graduation_market_cap_usd as u128) * (self.king_of_hill_bps as u128) / 10000) as u64
+    }
 }
 
-#[account]
+/// Hot trading state for a single mint, read in place on every trade instead
+/// of being Borsh-decoded. Display fields live in `TokenMetadata`. Fields are
+/// ordered so the layout has no implicit padding; flags are `u8`s, the `u128`
+/// accumulator is stored as bytes, and unset roots and deadlines are zero.
+#[account(zero_copy)]
 pub struct BondingCurve {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub allowlist_root: [u8; 32],
+    pub presale_merkle_root: [u8; 32],
     pub virtual_token_reserves: u64,
     pub virtual_sol_reserves: u64,
     pub real_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub token_total_supply: u64,
-    pub complete: bool,
+    pub created_at: i64,
+    pub presale_allocation: u64,
+    pub presale_tokens_sold: u64,
+    pub presale_sol_raised: u64,
+    pub presale_ends_at: i64,
+    pub last_price_update: i64,
+    pub trade_count: u64,
+    pub deadline: i64,
+    pub initial_real_token_reserves: u64,
+    pub refund_sol_pool: u64,
+    pub refund_token_supply: u64,
+    pub tokens_refunded: u64,
+    pub price_cumulative: [u8; 16],
+    pub complete: u8,
+    pub presale_finalized: u8,
     pub bump: u8,
+    pub version: u8,
+    pub _padding: [u8; 4],
+    pub reserved: [u8; 64],
 }
 
 impl BondingCurve {
     pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // creator
+        32 + // allowlist_root
+        32 + // presale_merkle_root
         8 + // virtual_token_reserves
         8 + // virtual_sol_reserves
         8 + // real_token_reserves
         8 + // real_sol_reserves
         8 + // token_total_supply
+        8 + // created_at
+        8 + // presale_allocation
+        8 + // presale_tokens_sold
+        8 + // presale_sol_raised
+        8 + // presale_ends_at
+        8 + // last_price_update
+        8 + // trade_count
+        8 + // deadline
+        8 + // initial_real_token_reserves
+        8 + // refund_sol_pool
+        8 + // refund_token_supply
+        8 + // tokens_refunded
+        16 + // price_cumulative
         1 + // complete
-        1; // bump
+        1 + // presale_finalized
+        1 + // bump
+        1 + // version
+        4 + // _padding
+        64; // reserved
 
-    pub fn calculate_buy_price(&self, token_amount: u64) -> Result<u64> {
-        if self.complete {
-            return Err(error!(ErrorCode::BondingCurveComplete));
-        }
+    /// Version 2 is the zero-copy layout; earlier versions were Borsh.
+    pub const VERSION: u8 = 2;
 
-        if token_amount == 0 {
-            return Ok(0);
+    pub const MIN_PRESALE_DURATION: i64 = 60 * 60;
+    pub const MAX_PRESALE_DURATION: i64 = 30 * 24 * 60 * 60;
+
+    pub const MIN_DEADLINE_DURATION: i64 = 24 * 60 * 60;
+    pub const MAX_DEADLINE_DURATION: i64 = 180 * 24 * 60 * 60;
+
+    /// Fixed-point scale applied to spot prices (lamports per token).
+    pub const PRICE_SCALE: u128 = 1_000_000_000;
+
+    /// A presale is configured by a non-zero end time and stays pending until
+    /// `finalize_presale` folds it into the public curve.
+    pub fn is_presale_pending(&self) -> bool {
+        self.presale_ends_at != 0 && !self.is_presale_finalized()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete != 0
+    }
+
+    pub fn set_complete(&mut self) {
+        self.complete = 1;
+    }
+
+    pub fn is_presale_finalized(&self) -> bool {
+        self.presale_finalized != 0
+    }
+
+    /// Allowlist gating public buys, if the creator set one.
+    pub fn allowlist_root(&self) -> Option<[u8; 32]> {
+        (self.allowlist_root != [0; 32]).then_some(self.allowlist_root)
+    }
+
+    pub fn price_cumulative(&self) -> u128 {
+        u128::from_le_bytes(self.price_cumulative)
+    }
+
+    /// SOL cost of `token_amount` at the initial curve price, rounded up.
+    /// Only meaningful while the presale is pending, before any public trade
+    /// has moved the virtual reserves.
+    pub fn calculate_presale_cost(&self, token_amount: u64) -> Result<u64> {
+        let numerator = (token_amount as u128)
+            .checked_mul(self.virtual_sol_reserves as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        let cost = numerator
+            .checked_add(self.virtual_token_reserves as u128 - 1)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(self.virtual_token_reserves as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        u64::try_from(cost).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+    }
+
+    /// Moves the virtual reserves by the aggregate presale so public trading
+    /// starts from the price the presale volume would have produced. Any
+    /// allocation that was never bought simply stays in the real reserves.
+    pub fn finalize_presale(&mut self) -> Result<()> {
+        self.virtual_sol_reserves = self.virtual_sol_reserves
+            .checked_add(self.presale_sol_raised)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.virtual_token_reserves = self.virtual_token_reserves
+            .checked_sub(self.presale_tokens_sold)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.presale_finalized = 1;
+
+        Ok(())
+    }
+
+    /// True once a curve with a deadline has failed to graduate by it. The
+    /// curve then stops trading and only accepts refunds.
+    pub fn is_past_deadline(&self, timestamp: i64) -> bool {
+        !self.is_complete() && self.deadline != 0 && timestamp >= self.deadline
+    }
+
+    /// Once a curve has missed its deadline or paid out a refund, its
+    /// reserves back the refunds owed to holders, so it can't be completed
+    /// or have its liquidity withdrawn.
+    pub fn require_not_refunding(&self, timestamp: i64) -> Result<()> {
+        require!(
+            !self.is_past_deadline(timestamp) && self.refund_token_supply == 0,
+            PumpCloneError::CurveDeadlinePassed
+        );
+
+        Ok(())
+    }
+
+    /// SOL owed for returning `token_amount` after the deadline. The first
+    /// refund snapshots the SOL reserves and the tokens sold off the curve so
+    /// every holder exits at the same price per token, whatever their order.
+    pub fn refund(&mut self, token_amount: u64) -> Result<u64> {
+        if self.refund_token_supply == 0 {
+            self.refund_token_supply = self.initial_real_token_reserves
+                .checked_sub(self.real_token_reserves)
+                .ok_or(PumpCloneError::ArithmeticOverflow)?;
+            self.refund_sol_pool = self.real_sol_reserves;
         }
 
-        let virtual_token_reserves = self.virtual_token_reserves;
-        let virtual_sol_reserves = self.virtual_sol_reserves;
+        let tokens_refunded = self.tokens_refunded
+            .checked_add(token_amount)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+        require!(
+            tokens_refunded <= self.refund_token_supply,
+            PumpCloneError::RefundExceedsTokensSold
+        );
+
+        let sol_amount = (token_amount as u128)
+            .checked_mul(self.refund_sol_pool as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(self.refund_token_supply as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+        let sol_amount = u64::try_from(sol_amount).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))?;
+
+        self.tokens_refunded = tokens_refunded;
+        self.real_token_reserves = self.real_token_reserves
+            .checked_add(token_amount)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_sub(sol_amount)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        Ok(sol_amount)
+    }
+
+    pub fn record_trade(&mut self) -> Result<()> {
+        self.trade_count = self.trade_count
+            .checked_add(1)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// A curve can be closed once it has migrated (completed and its SOL
+    /// reserves moved out) or, when `token_expiry` is set, once it has gone
+    /// that long after launch without a single trade.
+    pub fn is_closable(&self, token_expiry: i64, timestamp: i64) -> bool {
+        let migrated = self.is_complete() && self.real_sol_reserves == 0;
+        let expired = token_expiry > 0
+            && self.trade_count == 0
+            && timestamp >= self.created_at.saturating_add(token_expiry);
+
+        migrated || expired
+    }
+
+    /// Spot price in lamports per token, scaled by `PRICE_SCALE`.
+    pub fn spot_price(&self) -> Result<u64> {
+        let price = (self.virtual_sol_reserves as u128)
+            .checked_mul(Self::PRICE_SCALE)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(self.virtual_token_reserves as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
-        if virtual_token_reserves <= token_amount {
-            return Err(error!(ErrorCode::InsufficientTokenReserves));
+        u64::try_from(price).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+    }
+
+    /// Cumulative price as of `timestamp`, extending the stored accumulator by
+    /// the current spot price for the seconds elapsed since the last update.
+    /// Wraps on overflow like Uniswap v2; only differences are meaningful.
+    /// The accumulator only moves forward, so it can't be observed before
+    /// its last update.
+    pub fn observe(&self, timestamp: i64) -> Result<PriceObservation> {
+        require!(timestamp >= self.last_price_update, PumpCloneError::InvalidTimestamp);
+        let elapsed = (timestamp - self.last_price_update) as u128;
+        let price_cumulative = self.price_cumulative()
+            .wrapping_add((self.spot_price()? as u128).wrapping_mul(elapsed));
+
+        Ok(PriceObservation {
+            price_cumulative,
+            timestamp,
+        })
+    }
+
+    /// Rolls the accumulator forward to `timestamp`. Must run before the
+    /// reserves change so the elapsed interval is weighted by the price that
+    /// actually held during it.
+    pub fn accumulate_price(&mut self, timestamp: i64) -> Result<()> {
+        // A clock that hasn't moved past the last update adds nothing
+        if timestamp <= self.last_price_update {
+            return Ok(());
         }
 
-        // Calculate SOL amount using constant product formula: x * y = k
-        let k = virtual_token_reserves
-            .checked_mul(virtual_sol_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let observation = self.observe(timestamp)?;
+        self.price_cumulative = observation.price_cumulative.to_le_bytes();
+        self.last_price_update = timestamp;
 
-        let new_token_reserves = virtual_token_reserves
-            .checked_sub(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
 
-        let new_sol_reserves = k
-            .checked_div(new_token_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
+    /// Time-weighted average spot price (scaled by `PRICE_SCALE`) between two
+    /// observations, `older` taken first.
+    pub fn twap(older: &PriceObservation, newer: &PriceObservation) -> Result<u64> {
+        let elapsed = newer.timestamp
+            .checked_sub(older.timestamp)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+        require!(elapsed > 0, PumpCloneError::InvalidAmount);
 
-        let sol_amount = new_sol_reserves
-            .checked_sub(virtual_sol_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let average = newer.price_cumulative
+            .wrapping_sub(older.price_cumulative)
+            .checked_div(elapsed as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
-        Ok(sol_amount)
+        u64::try_from(average).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
     }
 
-    pub fn calculate_sell_price(&self, token_amount: u64) -> Result<u64> {
-        if self.complete {
-            return Err(error!(ErrorCode::BondingCurveComplete));
+    /// Fully diluted market cap in lamports at the current spot price.
+    pub fn market_cap(&self) -> Result<u64> {
+        let market_cap = (self.virtual_sol_reserves as u128)
+            .checked_mul(self.token_total_supply as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(self.virtual_token_reserves as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        u64::try_from(market_cap).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+    }
+
+    pub fn calculate_buy_price(&self, token_amount: u64) -> Result<u64> {
+        if self.is_complete() {
+            return Err(error!(PumpCloneError::TokenAlreadyGraduated));
         }
 
         if token_amount == 0 {
             return Ok(0);
         }
 
-        let virtual_token_reserves = self.virtual_token_reserves;
-        let virtual_sol_reserves = self.virtual_sol_reserves;
+        if self.virtual_token_reserves <= token_amount {
+            return Err(error!(PumpCloneError::InsufficientTokensForSale));
+        }
 
-        // Calculate SOL amount using constant product formula: x * y = k
-        let k = virtual_token_reserves
-            .checked_mul(virtual_sol_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
+        curve::sol_in_for_tokens(&CurveReserves::from(self), token_amount)
+    }
 
-        let new_token_reserves = virtual_token_reserves
-            .checked_add(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+    pub fn calculate_sell_price(&self, token_amount: u64) -> Result<u64> {
+        if self.is_complete() {
+            return Err(error!(PumpCloneError::TokenAlreadyGraduated));
+        }
 
-        let new_sol_reserves = k
-            .checked_div(new_token_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
+        if token_amount == 0 {
+            return Ok(0);
+        }
 
-        let sol_amount = virtual_sol_reserves
-            .checked_sub(new_sol_reserves)
-            .ok_or(ErrorCode::MathOverflow)?;
+        curve::sol_out_for_tokens(&CurveReserves::from(self), token_amount)
+    }
 
-        Ok(sol_amount)
+    /// Writes the reserves a curve quote left the curve at.
+    pub fn set_reserves(&mut self, reserves: &CurveReserves) {
+        self.virtual_sol_reserves = reserves.virtual_sol_reserves;
+        self.virtual_token_reserves = reserves.virtual_token_reserves;
+        self.real_sol_reserves = reserves.real_sol_reserves;
+        self.real_token_reserves = reserves.real_token_reserves;
     }
 
     pub fn update_reserves_buy(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
         self.virtual_token_reserves = self.virtual_token_reserves
             .checked_sub(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.virtual_sol_reserves = self.virtual_sol_reserves
             .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.real_sol_reserves = self.real_sol_reserves
             .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         Ok(())
     }
@@ -140,20 +425,220 @@ impl BondingCurve {
     pub fn update_reserves_sell(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
         self.virtual_token_reserves = self.virtual_token_reserves
             .checked_add(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.virtual_sol_reserves = self.virtual_sol_reserves
             .checked_sub(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.real_sol_reserves = self.real_sol_reserves
             .checked_sub(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         Ok(())
     }
 }
 
+/// Borsh layout `BondingCurve` had before accounts were versioned. Nothing
+/// marks it as version 0; it is told apart from version 1 by its size.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BondingCurveV0 {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    pub created_at: i64,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub presale_merkle_root: [u8; 32],
+    pub presale_allocation: u64,
+    pub presale_tokens_sold: u64,
+    pub presale_sol_raised: u64,
+    pub presale_ends_at: i64,
+    pub presale_finalized: bool,
+    pub price_cumulative: u128,
+    pub last_price_update: i64,
+    pub trade_count: u64,
+    pub deadline: Option<i64>,
+    pub initial_real_token_reserves: u64,
+    pub refund_sol_pool: u64,
+    pub refund_token_supply: u64,
+    pub tokens_refunded: u64,
+    pub bump: u8,
+}
+
+impl BondingCurveV0 {
+    pub const VERSION: u8 = 0;
+
+    pub const LEN: usize = LegacyBondingCurve::LEN -
+        1 - // version
+        64; // reserved
+
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        deserialize_legacy(data, Self::LEN)
+    }
+
+    /// Stamps the version and zeroes the reserved bytes version 1 appended.
+    pub fn into_v1(self) -> LegacyBondingCurve {
+        LegacyBondingCurve {
+            mint: self.mint,
+            creator: self.creator,
+            virtual_token_reserves: self.virtual_token_reserves,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            token_total_supply: self.token_total_supply,
+            complete: self.complete,
+            created_at: self.created_at,
+            allowlist_root: self.allowlist_root,
+            presale_merkle_root: self.presale_merkle_root,
+            presale_allocation: self.presale_allocation,
+            presale_tokens_sold: self.presale_tokens_sold,
+            presale_sol_raised: self.presale_sol_raised,
+            presale_ends_at: self.presale_ends_at,
+            presale_finalized: self.presale_finalized,
+            price_cumulative: self.price_cumulative,
+            last_price_update: self.last_price_update,
+            trade_count: self.trade_count,
+            deadline: self.deadline,
+            initial_real_token_reserves: self.initial_real_token_reserves,
+            refund_sol_pool: self.refund_sol_pool,
+            refund_token_supply: self.refund_token_supply,
+            tokens_refunded: self.tokens_refunded,
+            bump: self.bump,
+            version: LegacyBondingCurve::VERSION,
+            reserved: [0; 64],
+        }
+    }
+}
+
+/// Borsh layout `BondingCurve` had at version 1, kept so `upgrade_account`
+/// can rewrite existing curves in the zero-copy layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyBondingCurve {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    pub created_at: i64,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub presale_merkle_root: [u8; 32],
+    pub presale_allocation: u64,
+    pub presale_tokens_sold: u64,
+    pub presale_sol_raised: u64,
+    pub presale_ends_at: i64,
+    pub presale_finalized: bool,
+    pub price_cumulative: u128,
+    pub last_price_update: i64,
+    pub trade_count: u64,
+    pub deadline: Option<i64>,
+    pub initial_real_token_reserves: u64,
+    pub refund_sol_pool: u64,
+    pub refund_token_supply: u64,
+    pub tokens_refunded: u64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; 64],
+}
+
+impl LegacyBondingCurve {
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // creator
+        8 + // virtual_token_reserves
+        8 + // virtual_sol_reserves
+        8 + // real_token_reserves
+        8 + // real_sol_reserves
+        8 + // token_total_supply
+        1 + // complete
+        8 + // created_at
+        1 + 32 + // allowlist_root (Option<[u8; 32]>)
+        32 + // presale_merkle_root
+        8 + // presale_allocation
+        8 + // presale_tokens_sold
+        8 + // presale_sol_raised
+        8 + // presale_ends_at
+        1 + // presale_finalized
+        16 + // price_cumulative
+        8 + // last_price_update
+        8 + // trade_count
+        1 + 8 + // deadline (Option<i64>)
+        8 + // initial_real_token_reserves
+        8 + // refund_sol_pool
+        8 + // refund_token_supply
+        8 + // tokens_refunded
+        1 + // bump
+        1 + // version
+        64; // reserved
+
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        let curve: Self = deserialize_legacy(data, Self::LEN)?;
+        require!(curve.version == Self::VERSION, PumpCloneError::InvalidAccount);
+
+        Ok(curve)
+    }
+
+    /// Rewrites a version 1 curve in the zero-copy layout of version 2.
+    pub fn into_zero_copy(self) -> BondingCurve {
+        BondingCurve {
+            mint: self.mint,
+            creator: self.creator,
+            allowlist_root: self.allowlist_root.unwrap_or_default(),
+            presale_merkle_root: self.presale_merkle_root,
+            virtual_token_reserves: self.virtual_token_reserves,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            token_total_supply: self.token_total_supply,
+            created_at: self.created_at,
+            presale_allocation: self.presale_allocation,
+            presale_tokens_sold: self.presale_tokens_sold,
+            presale_sol_raised: self.presale_sol_raised,
+            presale_ends_at: self.presale_ends_at,
+            last_price_update: self.last_price_update,
+            trade_count: self.trade_count,
+            deadline: self.deadline.unwrap_or_default(),
+            initial_real_token_reserves: self.initial_real_token_reserves,
+            refund_sol_pool: self.refund_sol_pool,
+            refund_token_supply: self.refund_token_supply,
+            tokens_refunded: self.tokens_refunded,
+            price_cumulative: self.price_cumulative.to_le_bytes(),
+            complete: self.complete as u8,
+            presale_finalized: self.presale_finalized as u8,
+            bump: self.bump,
+            version: BondingCurve::VERSION,
+            _padding: [0; 4],
+            reserved: [0; 64],
+        }
+    }
+}
+
+/// Decodes a Borsh curve from an account of exactly `len` bytes, past its
+/// discriminator. Trailing bytes left by a `None` option are ignored.
+fn deserialize_legacy<T: AnchorDeserialize>(data: &[u8], len: usize) -> Result<T> {
+    require!(data.len() == len, PumpCloneError::InvalidAccount);
+
+    T::deserialize(&mut &data[8..])
+        .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))
+}
+
+/// A snapshot of `BondingCurve::price_cumulative` that integrators store and
+/// later pair with a fresh one to derive a TWAP.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct PriceObservation {
+    pub price_cumulative: u128,
+    pub timestamp: i64,
+}
+
 #[account]
 pub struct TokenMetadata {
     pub mint: Pubkey,
@@ -173,6 +658,8 @@ pub struct TokenMetadata {
     pub nsfw: bool,
     pub show_name: bool,
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; 64],
 }
 
 impl TokenMetadata {
@@ -199,47 +686,51 @@ impl TokenMetadata {
         8 + // king_of_hill_timestamp
         1 + // nsfw
         1 + // show_name
-        1; // bump
+        1 + // bump
+        1 + // version
+        64; // reserved
+
+    pub const VERSION: u8 = 1;
 
     pub fn validate_metadata(&self) -> Result<()> {
         require!(
             self.name.len() <= Self::MAX_NAME_LEN && !self.name.is_empty(),
-            ErrorCode::InvalidTokenName
+            PumpCloneError::TokenNameTooLong
         );
 
         require!(
             self.symbol.len() <= Self::MAX_SYMBOL_LEN && !self.symbol.is_empty(),
-            ErrorCode::InvalidTokenSymbol
+            PumpCloneError::TokenSymbolTooLong
         );
 
         require!(
             self.description.len() <= Self::MAX_DESCRIPTION_LEN,
-            ErrorCode::InvalidTokenDescription
+            PumpCloneError::TokenDescriptionTooLong
         );
 
         require!(
             self.image_uri.len() <= Self::MAX_URI_LEN,
-            ErrorCode::InvalidImageUri
+            PumpCloneError::InvalidMetadataUri
         );
 
         require!(
             self.metadata_uri.len() <= Self::MAX_URI_LEN,
-            ErrorCode::InvalidMetadataUri
+            PumpCloneError::InvalidMetadataUri
         );
 
         require!(
             self.twitter.len() <= Self::MAX_SOCIAL_LEN,
-            ErrorCode::InvalidSocialLink
+            PumpCloneError::InvalidSocialMediaLinks
         );
 
         require!(
             self.telegram.len() <= Self::MAX_SOCIAL_LEN,
-            ErrorCode::InvalidSocialLink
+            PumpCloneError::InvalidSocialMediaLinks
         );
 
         require!(
             self.website.len() <= Self::MAX_SOCIAL_LEN,
-            ErrorCode::InvalidSocialLink
+            PumpCloneError::InvalidSocialMediaLinks
         );
 
         Ok(())
@@ -259,6 +750,8 @@ pub struct UserStats {
     pub first_trade_timestamp: i64,
     pub last_trade_timestamp: i64,
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; 32],
 }
 
 impl UserStats {
@@ -273,24 +766,46 @@ impl UserStats {
         8 + // total_sol_received
         8 + // first_trade_timestamp
         8 + // last_trade_timestamp
-        1; // bump
+        1 + // bump
+        1 + // version
+        32; // reserved
+
+    pub const VERSION: u8 = 1;
+
+    /// Fills in the identity fields the first time an `init_if_needed`
+    /// stats account is touched.
+    pub fn ensure_initialized(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+            self.version = Self::VERSION;
+        }
+    }
+
+    pub fn record_token_created(&mut self) -> Result<()> {
+        self.tokens_created = self.tokens_created
+            .checked_add(1)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
 
     pub fn update_buy_stats(&mut self, token_amount: u64, sol_amount: u64, fees: u64, timestamp: i64) -> Result<()> {
         self.total_tokens_bought = self.total_tokens_bought
             .checked_add(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.total_sol_spent = self.total_sol_spent
             .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.total_volume_traded = self.total_volume_traded
             .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.total_fees_paid = self.total_fees_paid
             .checked_add(fees)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         if self.first_trade_timestamp == 0 {
             self.first_trade_timestamp = timestamp;
@@ -303,19 +818,19 @@ impl UserStats {
     pub fn update_sell_stats(&mut self, token_amount: u64, sol_amount: u64, fees: u64, timestamp: i64) -> Result<()> {
         self.total_tokens_sold = self.total_tokens_sold
             .checked_add(token_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.total_sol_received = self.total_sol_received
             .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.total_volume_traded = self.total_volume_traded
             .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         self.total_fees_paid = self.total_fees_paid
             .checked_add(fees)
-            .ok_or(ErrorCode::MathOverflow)?;
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
 
         if self.first_trade_timestamp == 0 {
             self.first_trade_timestamp = timestamp;
@@ -327,33 +842,214 @@ impl UserStats {
 }
 
 #[account]
-pub struct TradeHistory {
+pub struct Position {
     pub user: Pubkey,
     pub mint: Pubkey,
-    pub is_buy: bool,
-    pub token_amount: u64,
-    pub sol_amount: u64,
-    pub virtual_sol_reserves: u64,
-    pub virtual_token_reserves: u64,
-    pub timestamp: i64,
-    pub signature: String,
+    pub token_balance: u64,
+    pub cost_basis: u64,
+    pub average_entry_price: u64,
+    pub total_sol_spent: u64,
+    pub total_sol_received: u64,
+    pub realized_pnl: i64,
+    pub opened_at: i64,
+    pub last_updated: i64,
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; 32],
 }
 
-impl TradeHistory {
-    pub const MAX_SIGNATURE_LEN: usize = 88;
-
+impl Position {
     pub const LEN: usize = 8 + // discriminator
         32 + // user
         32 + // mint
-        1 + // is_buy
-        8 + // token_amount
-        8 + // sol_amount
-        8 + // virtual_sol_reserves
-        8 + // virtual_token_reserves
-        8 + // timestamp
-        4 + Self::MAX_SIGNATURE_LEN + // signature
-        1; // bump
+        8 + // token_balance
+        8 + // cost_basis
+        8 + // average_entry_price
+        8 + // total_sol_spent
+        8 + // total_sol_received
+        8 + // realized_pnl
+        8 + // opened_at
+        8 + // last_updated
+        1 + // bump
+        1 + // version
+        32; // reserved
+
+    pub const VERSION: u8 = 1;
+
+    /// `average_entry_price` uses the same fixed-point scale as curve prices.
+    pub const PRICE_SCALE: u128 = BondingCurve::PRICE_SCALE;
+
+    pub fn ensure_initialized(&mut self, user: Pubkey, mint: Pubkey, bump: u8, timestamp: i64) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.mint = mint;
+            self.opened_at = timestamp;
+            self.bump = bump;
+            self.version = Self::VERSION;
+        }
+    }
+
+    pub fn record_buy(&mut self, token_amount: u64, sol_spent: u64, timestamp: i64) -> Result<()> {
+        self.token_balance = self.token_balance
+            .checked_add(token_amount)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.cost_basis = self.cost_basis
+            .checked_add(sol_spent)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.total_sol_spent = self.total_sol_spent
+            .checked_add(sol_spent)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.refresh_average_entry_price()?;
+        self.last_updated = timestamp;
+
+        Ok(())
+    }
+
+    /// Realizes PnL against the average cost of the tracked balance. Tokens
+    /// sold beyond that balance were acquired outside the program, so only
+    /// the matching share of the proceeds is counted towards PnL.
+    pub fn record_sell(&mut self, token_amount: u64, sol_received: u64, timestamp: i64) -> Result<()> {
+        self.total_sol_received = self.total_sol_received
+            .checked_add(sol_received)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+        self.last_updated = timestamp;
+
+        let tracked_amount = token_amount.min(self.token_balance);
+        if tracked_amount == 0 {
+            return Ok(());
+        }
+
+        let cost_removed = (self.cost_basis as u128)
+            .checked_mul(tracked_amount as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(self.token_balance as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        let tracked_proceeds = (sol_received as u128)
+            .checked_mul(tracked_amount as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(token_amount as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        let pnl = i64::try_from(tracked_proceeds as i128 - cost_removed as i128)
+            .map_err(|_| error!(PumpCloneError::ArithmeticOverflow))?;
+
+        self.realized_pnl = self.realized_pnl
+            .checked_add(pnl)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.cost_basis = self.cost_basis
+            .checked_sub(cost_removed as u64)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.token_balance = self.token_balance
+            .checked_sub(tracked_amount)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.refresh_average_entry_price()
+    }
+
+    fn refresh_average_entry_price(&mut self) -> Result<()> {
+        if self.token_balance == 0 {
+            self.cost_basis = 0;
+            self.average_entry_price = 0;
+            return Ok(());
+        }
+
+        let price = (self.cost_basis as u128)
+            .checked_mul(Self::PRICE_SCALE)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(self.token_balance as u128)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        self.average_entry_price = u64::try_from(price)
+            .map_err(|_| error!(PumpCloneError::ArithmeticOverflow))?;
+
+        Ok(())
+    }
+}
+
+#[zero_copy]
+#[derive(Default)]
+pub struct Candle {
+    pub open_time: i64,
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+    pub volume: u64,
+}
+
+/// Ring buffer of 1-minute OHLCV candles for a single mint. Prices are
+/// curve spot prices scaled by `BondingCurve::PRICE_SCALE` and volume is in
+/// lamports, so a chart can be rendered from this one account.
+#[account(zero_copy)]
+pub struct PriceHistory {
+    pub mint: Pubkey,
+    pub head: u64,
+    pub len: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub candles: [Candle; PriceHistory::MAX_CANDLES],
+}
+
+impl PriceHistory {
+    pub const MAX_CANDLES: usize = 200;
+    pub const CANDLE_INTERVAL: i64 = 60;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        8 + // head
+        8 + // len
+        1 + // bump
+        7 + // _padding
+        48 * Self::MAX_CANDLES; // candles
+
+    /// Folds a trade into the current candle, or opens a new one (evicting
+    /// the oldest once the buffer is full) when the trade starts a new
+    /// minute. New candles open at the previous close so the chart has no
+    /// gaps between trades.
+    pub fn record(&mut self, timestamp: i64, price: u64, volume: u64) -> Result<()> {
+        let open_time = timestamp - timestamp.rem_euclid(Self::CANDLE_INTERVAL);
+
+        if self.len > 0 {
+            let current = &mut self.candles[self.head as usize];
+            if open_time <= current.open_time {
+                current.high = current.high.max(price);
+                current.low = current.low.min(price);
+                current.close = price;
+                current.volume = current.volume
+                    .checked_add(volume)
+                    .ok_or(PumpCloneError::ArithmeticOverflow)?;
+                return Ok(());
+            }
+        }
+
+        let open = if self.len > 0 {
+            self.candles[self.head as usize].close
+        } else {
+            price
+        };
+
+        if self.len > 0 {
+            self.head = (self.head + 1) % Self::MAX_CANDLES as u64;
+        }
+        self.len = (self.len + 1).min(Self::MAX_CANDLES as u64);
+
+        self.candles[self.head as usize] = Candle {
+            open_time,
+            open,
+            high: open.max(price),
+            low: open.min(price),
+            close: price,
+            volume,
+        };
+
+        Ok(())
+    }
 }
 
 #[account]
@@ -366,6 +1062,8 @@ pub struct Comment {
     pub likes: u32,
     pub replies: u32,
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; 32],
 }
 
 impl Comment {
@@ -379,57 +1077,756 @@ impl Comment {
         1 + 32 + // reply_to (Option<Pubkey>)
         4 + // likes
         4 + // replies
-        1; // bump
+        1 + // bump
+        1 + // version
+        32; // reserved
+
+    pub const VERSION: u8 = 1;
 
     pub fn validate_content(&self) -> Result<()> {
         require!(
             !self.content.is_empty() && self.content.len() <= Self::MAX_CONTENT_LEN,
-            ErrorCode::InvalidCommentContent
+            PumpCloneError::InvalidCommentContent
         );
         Ok(())
     }
 }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Math overflow")]
-    MathOverflow,
-    #[msg("Bonding curve is complete")]
-    BondingCurveComplete,
-    #[msg("Insufficient token reserves")]
-    InsufficientTokenReserves,
-    #[msg("Invalid token name")]
-    InvalidTokenName,
-    #[msg("Invalid token symbol")]
-    InvalidTokenSymbol,
-    #[msg("Invalid token description")]
-    InvalidTokenDescription,
-    #[msg("Invalid image URI")]
-    InvalidImageUri,
-    #[msg("Invalid metadata URI")]
-    InvalidMetadataUri,
-    #[msg("Invalid social link")]
-    InvalidSocialLink,
-    #[msg("Invalid comment content")]
-    InvalidCommentContent,
-    #[msg("Slippage tolerance exceeded")]
-    SlippageExceeded,
-    #[msg("Insufficient SOL balance")]
-    InsufficientSolBalance,
-    #[msg("Insufficient token balance")]
-    InsufficientTokenBalance,
-    #[msg("Program is paused")]
-    ProgramPaused,
-    #[msg("Unauthorized")]
-    Unauthorized,
-    #[msg("Invalid fee percentage")]
-    InvalidFeePercentage,
-    #[msg("Migration threshold not met")]
-    MigrationThresholdNotMet,
-    #[msg("Token already migrated")]
-    TokenAlreadyMigrated,
-    #[msg("Invalid amount")]
-    InvalidAmount,
-    #[msg("Market cap too low")]
-    MarketCapTooLow,
-    #[msg("Market cap too high")]
\ No newline at end of file
+#[account]
+pub struct CommentLike {
+    pub comment: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
+}
+
+impl CommentLike {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // comment
+        32 + // user
+        1; // bump
+}
+
+#[account]
+pub struct KingOfTheHill {
+    pub mint: Pubkey,
+    pub market_cap_usd: u64,
+    pub crowned_at: i64,
+    pub bump: u8,
+}
+
+impl KingOfTheHill {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        8 + // market_cap_usd
+        8 + // crowned_at
+        1; // bump
+
+    /// Refreshes the current king's market cap, or crowns `mint` if it has
+    /// overtaken it. Returns true when the crown changes hands.
+    pub fn update(&mut self, mint: Pubkey, market_cap_usd: u64, timestamp: i64) -> bool {
+        if self.mint == mint {
+            self.market_cap_usd = market_cap_usd;
+            return false;
+        }
+
+        if market_cap_usd <= self.market_cap_usd {
+            return false;
+        }
+
+        self.mint = mint;
+        self.market_cap_usd = market_cap_usd;
+        self.crowned_at = timestamp;
+
+        true
+    }
+}
+
+#[account]
+pub struct PresaleReceipt {
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub tokens_purchased: u64,
+    pub sol_spent: u64,
+    pub bump: u8,
+}
+
+impl PresaleReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // buyer
+        32 + // mint
+        8 + // tokens_purchased
+        8 + // sol_spent
+        1; // bump
+}
+
+#[account]
+pub struct VestingEscrow {
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_timestamp: i64,
+    pub cliff_timestamp: i64,
+    pub end_timestamp: i64,
+    pub bump: u8,
+}
+
+impl VestingEscrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // beneficiary
+        8 + // total_amount
+        8 + // claimed_amount
+        8 + // start_timestamp
+        8 + // cliff_timestamp
+        8 + // end_timestamp
+        1; // bump
+
+    /// Nothing unlocks before the cliff; after it the allocation vests
+    /// linearly from `start_timestamp` until `end_timestamp`.
+    pub fn vested_amount(&self, timestamp: i64) -> Result<u64> {
+        if timestamp < self.cliff_timestamp {
+            return Ok(0);
+        }
+
+        if timestamp >= self.end_timestamp {
+            return Ok(self.total_amount);
+        }
+
+        let elapsed = (timestamp - self.start_timestamp) as u128;
+        let duration = (self.end_timestamp - self.start_timestamp) as u128;
+
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?
+            .checked_div(duration)
+            .ok_or(PumpCloneError::ArithmeticOverflow)?;
+
+        u64::try_from(vested).map_err(|_| error!(PumpCloneError::ArithmeticOverflow))
+    }
+
+    pub fn claimable_amount(&self, timestamp: i64) -> Result<u64> {
+        let vested = self.vested_amount(timestamp)?;
+
+        vested
+            .checked_sub(self.claimed_amount)
+            .ok_or(error!(PumpCloneError::ArithmeticOverflow))
+    }
+}
+
+#[account]
+pub struct AdminMultisig {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    /// Bumped whenever the signer set changes. Approvals are bit positions
+    /// into `signers`, so proposals from an earlier set can't be approved or
+    /// executed.
+    pub signers_version: u32,
+    pub bump: u8,
+}
+
+impl AdminMultisig {
+    pub const MAX_SIGNERS: usize = 10;
+
+    pub const LEN: usize = 8 + // discriminator
+        4 + 32 * Self::MAX_SIGNERS + // signers
+        1 + // threshold
+        8 + // proposal_count
+        4 + // signers_version
+        1; // bump
+
+    pub fn signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.signers.iter().position(|signer| signer == key)
+    }
+
+    pub fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= Self::MAX_SIGNERS,
+            PumpCloneError::InvalidMultiSigConfig
+        );
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            PumpCloneError::InvalidMultiSigConfig
+        );
+
+        for (i, signer) in signers.iter().enumerate() {
+            require!(
+                !signers[..i].contains(signer),
+                PumpCloneError::InvalidMultiSigConfig
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// GlobalState parameters an admin can change. `None` leaves a field as is.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub platform_fee_bps: Option<u16>,
+    pub creator_fee_bps: Option<u16>,
+    pub migration_fee: Option<u64>,
+    pub max_creator_allocation_bps: Option<u16>,
+    pub graduation_market_cap_usd: Option<u64>,
+    pub king_of_hill_bps: Option<u16>,
+    pub oracle_feed: Option<Pubkey>,
+    pub oracle_max_staleness: Option<i64>,
+    pub oracle_max_confidence_bps: Option<u16>,
+    pub token_expiry: Option<i64>,
+    pub config_timelock: Option<i64>,
+}
+
+impl ConfigUpdate {
+    pub const MAX_FEE_BPS: u16 = 1000;
+    pub const MAX_CONFIG_TIMELOCK: i64 = 30 * 24 * 60 * 60;
+
+    pub const LEN: usize = 1 + 2 + // platform_fee_bps
+        1 + 2 + // creator_fee_bps
+        1 + 8 + // migration_fee
+        1 + 2 + // max_creator_allocation_bps
+        1 + 8 + // graduation_market_cap_usd
+        1 + 2 + // king_of_hill_bps
+        1 + 32 + // oracle_feed
+        1 + 8 + // oracle_max_staleness
+        1 + 2 + // oracle_max_confidence_bps
+        1 + 8 + // token_expiry
+        1 + 8; // config_timelock
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(fee) = self.platform_fee_bps {
+            require!(fee <= Self::MAX_FEE_BPS, PumpCloneError::FeeTooHigh);
+        }
+
+        if let Some(fee) = self.creator_fee_bps {
+            require!(fee <= Self::MAX_FEE_BPS, PumpCloneError::FeeTooHigh);
+        }
+
+        if let Some(bps) = self.max_creator_allocation_bps {
+            require!(bps <= 10000, PumpCloneError::InvalidConfigurationParams);
+        }
+
+        if let Some(bps) = self.king_of_hill_bps {
+            require!(bps <= 10000, PumpCloneError::InvalidConfigurationParams);
+        }
+
+        if let Some(feed) = self.oracle_feed {
+            require!(feed != Pubkey::default(), PumpCloneError::InvalidPriceFeed);
+        }
+
+        if let Some(staleness) = self.oracle_max_staleness {
+            require!(staleness > 0, PumpCloneError::InvalidConfigurationParams);
+        }
+
+        if let Some(bps) = self.oracle_max_confidence_bps {
+            require!(bps <= 10000, PumpCloneError::InvalidConfigurationParams);
+        }
+
+        if let Some(expiry) = self.token_expiry {
+            require!(expiry >= 0, PumpCloneError::InvalidConfigurationParams);
+        }
+
+        if let Some(delay) = self.config_timelock {
+            require!(
+                (0..=Self::MAX_CONFIG_TIMELOCK).contains(&delay),
+                PumpCloneError::InvalidConfigurationParams
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn apply(&self, global_state: &mut GlobalState) {
+        if let Some(fee) = self.platform_fee_bps {
+            global_state.platform_fee_bps = fee;
+        }
+
+        if let Some(fee) = self.creator_fee_bps {
+            global_state.creator_fee_bps = fee;
+        }
+
+        if let Some(fee) = self.migration_fee {
+            global_state.migration_fee = fee;
+        }
+
+        if let Some(bps) = self.max_creator_allocation_bps {
+            global_state.max_creator_allocation_bps = bps;
+        }
+
+        if let Some(market_cap) = self.graduation_market_cap_usd {
+            global_state.graduation_market_cap_usd = market_cap;
+        }
+
+        if let Some(bps) = self.king_of_hill_bps {
+            global_state.king_of_hill_bps = bps;
+        }
+
+        if let Some(feed) = self.oracle_feed {
+            global_state.oracle_feed = feed;
+        }
+
+        if let Some(staleness) = self.oracle_max_staleness {
+            global_state.oracle_max_staleness = staleness;
+        }
+
+        if let Some(bps) = self.oracle_max_confidence_bps {
+            global_state.oracle_max_confidence_bps = bps;
+        }
+
+        if let Some(expiry) = self.token_expiry {
+            global_state.token_expiry = expiry;
+        }
+
+        if let Some(delay) = self.config_timelock {
+            global_state.config_timelock = delay;
+        }
+    }
+}
+
+#[account]
+pub struct PendingConfigChange {
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub update: ConfigUpdate,
+    pub queued_at: i64,
+    pub eta: i64,
+    pub bump: u8,
+}
+
+impl PendingConfigChange {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // index
+        32 + // proposer
+        ConfigUpdate::LEN + // update
+        8 + // queued_at
+        8 + // eta
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AdminAction {
+    UpdateConfig(ConfigUpdate),
+    WithdrawFees { amount: u64, recipient: Pubkey },
+    ProposeAuthority { new_authority: Pubkey },
+    AcceptAuthority,
+    SetFeeRecipient { fee_recipient: Pubkey },
+    SetGuardian { guardian: Pubkey },
+    Unpause { scopes: u8 },
+    BlockWallet { wallet: Pubkey, reason: String },
+    UnblockWallet { wallet: Pubkey },
+    ClearAllowlist { mint: Pubkey },
+    CancelConfigChange { index: u64 },
+    UpgradeAccount { kind: VersionedAccount },
+    /// Replaces the multisig's signers; only the multisig can execute it.
+    SetSigners { signers: Vec<Pubkey>, threshold: u8 },
+    /// Completes the curve of `mint` once it has reached the graduation
+    /// market cap, as `complete_bonding_curve` does.
+    CompleteBondingCurve { mint: Pubkey },
+    /// Pays the completed curve of `mint` out to the fee recipient, as
+    /// `withdraw` does for the authority.
+    Withdraw { mint: Pubkey },
+}
+
+impl AdminAction {
+    pub const LEN: usize = 1 + // variant tag
+        4 + 32 * AdminMultisig::MAX_SIGNERS + 1; // largest variant (SetSigners)
+
+    /// Checks that can be made when the action is proposed rather than
+    /// failing only once it has been approved.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            AdminAction::UpdateConfig(update) => update.validate(),
+            AdminAction::BlockWallet { reason, .. } => {
+                require!(
+                    reason.len() <= BlockedWallet::MAX_REASON_LEN,
+                    PumpCloneError::BlockReasonTooLong
+                );
+                Ok(())
+            }
+            AdminAction::SetSigners { signers, threshold } => {
+                AdminMultisig::validate_signers(signers, *threshold)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[account]
+pub struct AdminProposal {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub approvals: u16,
+    pub executed: bool,
+    pub created_at: i64,
+    pub signers_version: u32,
+    pub bump: u8,
+}
+
+impl AdminProposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // multisig
+        8 + // index
+        32 + // proposer
+        AdminAction::LEN + // action
+        2 + // approvals (bitmask over multisig signers)
+        1 + // executed
+        8 + // created_at
+        4 + // signers_version
+        1; // bump
+
+    pub fn has_approved(&self, signer_index: usize) -> bool {
+        self.approvals & (1 << signer_index) != 0
+    }
+
+    pub fn approve(&mut self, signer_index: usize) {
+        self.approvals |= 1 << signer_index;
+    }
+
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+}
+
+#[account]
+pub struct BlockedWallet {
+    pub wallet: Pubkey,
+    pub blocked_by: Pubkey,
+    pub reason: String,
+    pub blocked_at: i64,
+    pub bump: u8,
+}
+
+impl BlockedWallet {
+    pub const MAX_REASON_LEN: usize = 64;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // blocked_by
+        4 + Self::MAX_REASON_LEN + // reason
+        8 + // blocked_at
+        1; // bump
+}
+
+/// Accounts that carry a layout version and can be brought up to date in
+/// place by `upgrade_account`. Accounts written before versioning existed
+/// read as version 0.
+pub trait Versioned: AccountSerialize + AccountDeserialize + anchor_lang::Discriminator {
+    const CURRENT_VERSION: u8;
+    const SPACE: usize;
+
+    fn version(&self) -> u8;
+
+    /// Brings an older layout up to `CURRENT_VERSION`. Fields appended since
+    /// the account was written read as zero after the realloc.
+    fn migrate(&mut self);
+}
+
+/// Selects the layout `upgrade_account` migrates the target account as.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersionedAccount {
+    GlobalState,
+    BondingCurve,
+    TokenMetadata,
+    Comment,
+    UserStats,
+    Position,
+}
+
+macro_rules! impl_versioned {
+    ($($account:ty),*) => {
+        $(
+            impl Versioned for $account {
+                const CURRENT_VERSION: u8 = <$account>::VERSION;
+                const SPACE: usize = <$account>::LEN;
+
+                fn version(&self) -> u8 {
+                    self.version
+                }
+
+                fn migrate(&mut self) {
+                    self.version = Self::CURRENT_VERSION;
+                }
+            }
+        )*
+    };
+}
+
+impl_versioned!(GlobalState, TokenMetadata, Comment, UserStats, Position);
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    fn position() -> Position {
+        Position {
+            user: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            token_balance: 0,
+            cost_basis: 0,
+            average_entry_price: 0,
+            total_sol_spent: 0,
+            total_sol_received: 0,
+            realized_pnl: 0,
+            opened_at: 0,
+            last_updated: 0,
+            bump: 0,
+            version: Position::VERSION,
+            reserved: [0; 32],
+        }
+    }
+
+    /// 1,000 tokens bought for 1,000,000 lamports: 1,000 lamports a token.
+    fn position_after_buy() -> Position {
+        let mut position = position();
+        position.record_buy(1_000, 1_000_000, 1).unwrap();
+        position
+    }
+
+    #[test]
+    fn partial_sell_realizes_pnl_on_the_share_sold() {
+        let mut position = position_after_buy();
+        let average_entry_price = position.average_entry_price;
+
+        position.record_sell(400, 600_000, 2).unwrap();
+
+        assert_eq!(position.token_balance, 600);
+        assert_eq!(position.cost_basis, 600_000);
+        assert_eq!(position.realized_pnl, 200_000);
+        assert_eq!(position.total_sol_received, 600_000);
+        assert_eq!(position.average_entry_price, average_entry_price);
+        assert_eq!(position.last_updated, 2);
+    }
+
+    #[test]
+    fn full_sell_closes_out_the_cost_basis() {
+        let mut position = position_after_buy();
+
+        position.record_sell(1_000, 1_500_000, 2).unwrap();
+
+        assert_eq!(position.token_balance, 0);
+        assert_eq!(position.cost_basis, 0);
+        assert_eq!(position.average_entry_price, 0);
+        assert_eq!(position.realized_pnl, 500_000);
+        assert_eq!(position.total_sol_spent, 1_000_000);
+        assert_eq!(position.total_sol_received, 1_500_000);
+    }
+
+    #[test]
+    fn sell_below_cost_realizes_a_loss() {
+        let mut position = position_after_buy();
+
+        position.record_sell(500, 200_000, 2).unwrap();
+        assert_eq!(position.realized_pnl, -300_000);
+
+        position.record_sell(500, 100_000, 3).unwrap();
+        assert_eq!(position.realized_pnl, -700_000);
+        assert_eq!(position.token_balance, 0);
+    }
+
+    #[test]
+    fn trades_in_the_same_minute_merge_into_one_candle() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+
+        history.record(60, 100, 10).unwrap();
+        history.record(75, 150, 5).unwrap();
+        history.record(119, 80, 1).unwrap();
+
+        assert_eq!(history.len, 1);
+        let candle = history.candles[history.head as usize];
+        assert_eq!(candle.open_time, 60);
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.close, candle.volume),
+            (100, 150, 80, 80, 16)
+        );
+
+        // The next minute opens at the previous close
+        history.record(120, 90, 2).unwrap();
+
+        assert_eq!(history.len, 2);
+        assert_eq!(history.head, 1);
+        let candle = history.candles[1];
+        assert_eq!(candle.open_time, 120);
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.close, candle.volume),
+            (80, 90, 80, 90, 2)
+        );
+    }
+
+    #[test]
+    fn full_history_wraps_over_the_oldest_candle() {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        let interval = PriceHistory::CANDLE_INTERVAL;
+
+        for minute in 0..PriceHistory::MAX_CANDLES as i64 {
+            history.record(minute * interval, minute as u64 + 1, 1).unwrap();
+        }
+        assert_eq!(history.len, PriceHistory::MAX_CANDLES as u64);
+        assert_eq!(history.head, PriceHistory::MAX_CANDLES as u64 - 1);
+        assert_eq!(history.candles[0].open_time, 0);
+
+        let wrapped_at = PriceHistory::MAX_CANDLES as i64 * interval;
+        history.record(wrapped_at, 1_000, 1).unwrap();
+
+        assert_eq!(history.len, PriceHistory::MAX_CANDLES as u64);
+        assert_eq!(history.head, 0);
+        assert_eq!(history.candles[0].open_time, wrapped_at);
+        assert_eq!(history.candles[0].open, PriceHistory::MAX_CANDLES as u64);
+        assert_eq!(history.candles[0].close, 1_000);
+        // The oldest candle left is now the one after the head
+        assert_eq!(history.candles[1].open_time, interval);
+    }
+
+    /// A curve priced at exactly 1 lamport a token.
+    fn curve_at_par(timestamp: i64) -> BondingCurve {
+        let mut curve: BondingCurve = bytemuck::Zeroable::zeroed();
+        curve.virtual_sol_reserves = 1_000_000;
+        curve.virtual_token_reserves = 1_000_000;
+        curve.created_at = timestamp;
+        curve.last_price_update = timestamp;
+        curve
+    }
+
+    #[test]
+    fn twap_survives_the_accumulator_wrapping() {
+        let mut curve = curve_at_par(100);
+        let spot_price = curve.spot_price().unwrap();
+        curve.price_cumulative = (u128::MAX - spot_price as u128).to_le_bytes();
+
+        let older = curve.observe(100).unwrap();
+        curve.accumulate_price(110).unwrap();
+        let newer = curve.observe(130).unwrap();
+
+        assert!(newer.price_cumulative < older.price_cumulative);
+        assert_eq!(BondingCurve::twap(&older, &newer).unwrap(), spot_price);
+    }
+
+    #[test]
+    fn twap_weights_each_price_by_how_long_it_held() {
+        let mut curve = curve_at_par(0);
+        let older = curve.observe(0).unwrap();
+
+        // Price doubles after 30 seconds and holds for 10 more
+        curve.accumulate_price(30).unwrap();
+        curve.virtual_sol_reserves *= 2;
+        let newer = curve.observe(40).unwrap();
+
+        let par = BondingCurve::PRICE_SCALE as u64;
+        assert_eq!(
+            BondingCurve::twap(&older, &newer).unwrap(),
+            (par * 30 + 2 * par * 10) / 40
+        );
+    }
+
+    #[test]
+    fn twap_over_no_elapsed_time_is_rejected() {
+        let mut curve = curve_at_par(50);
+        let observation = curve.observe(50).unwrap();
+
+        // Accumulating without time passing leaves the accumulator alone
+        curve.accumulate_price(50).unwrap();
+        assert_eq!(curve.observe(50).unwrap().price_cumulative, observation.price_cumulative);
+
+        let error = BondingCurve::twap(&observation, &observation).unwrap_err();
+        assert_eq!(error, PumpCloneError::InvalidAmount.into());
+    }
+
+    #[test]
+    fn twap_window_cannot_reach_back_before_the_history() {
+        let mut curve = curve_at_par(1_000);
+        curve.accumulate_price(1_500).unwrap();
+
+        // The accumulator holds nothing from before its last update
+        let error = curve.observe(0).unwrap_err();
+        assert_eq!(error, PumpCloneError::InvalidTimestamp.into());
+
+        // A clock running behind the last update doesn't rewind it
+        curve.accumulate_price(1_200).unwrap();
+        assert_eq!(curve.last_price_update, 1_500);
+    }
+
+    fn curve_v0() -> BondingCurveV0 {
+        BondingCurveV0 {
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            virtual_token_reserves: 1_000_000,
+            virtual_sol_reserves: 30_000,
+            real_token_reserves: 800_000,
+            real_sol_reserves: 5_000,
+            token_total_supply: 1_000_000,
+            complete: false,
+            created_at: 100,
+            allowlist_root: None,
+            presale_merkle_root: [7; 32],
+            presale_allocation: 0,
+            presale_tokens_sold: 0,
+            presale_sol_raised: 0,
+            presale_ends_at: 0,
+            presale_finalized: false,
+            price_cumulative: 1 << 70,
+            last_price_update: 200,
+            trade_count: 3,
+            deadline: Some(10_000),
+            initial_real_token_reserves: 800_000,
+            refund_sol_pool: 0,
+            refund_token_supply: 0,
+            tokens_refunded: 0,
+            bump: 254,
+        }
+    }
+
+    fn account_data(curve: &impl AnchorSerialize, len: usize) -> Vec<u8> {
+        let mut data = BondingCurve::DISCRIMINATOR.to_vec();
+        curve.serialize(&mut data).unwrap();
+        data.resize(len, 0);
+        data
+    }
+
+    #[test]
+    fn version_0_curve_migrates_through_each_version() {
+        let v0 = curve_v0();
+        let data = account_data(&v0, BondingCurveV0::LEN);
+
+        let v1 = BondingCurveV0::try_from_account_data(&data).unwrap().into_v1();
+        assert_eq!(v1.version, LegacyBondingCurve::VERSION);
+        assert_eq!(v1.reserved, [0; 64]);
+        assert_eq!(v1.mint, v0.mint);
+        assert_eq!(v1.deadline, Some(10_000));
+
+        // Version 1 round-trips through its own account layout
+        let data = account_data(&v1, LegacyBondingCurve::LEN);
+        let v1 = LegacyBondingCurve::try_from_account_data(&data).unwrap();
+
+        let v2 = v1.into_zero_copy();
+        assert_eq!(v2.version, BondingCurve::VERSION);
+        assert_eq!(v2.mint, v0.mint);
+        assert_eq!(v2.creator, v0.creator);
+        assert_eq!(v2.real_sol_reserves, 5_000);
+        assert_eq!(v2.allowlist_root, [0; 32]);
+        assert_eq!(v2.presale_merkle_root, [7; 32]);
+        assert_eq!(u128::from_le_bytes(v2.price_cumulative), 1 << 70);
+        assert_eq!(v2.deadline, 10_000);
+        assert_eq!(v2.bump, 254);
+    }
+
+    #[test]
+    fn legacy_curves_of_the_wrong_size_or_version_are_rejected() {
+        let v0 = curve_v0();
+
+        // A version 0 account doesn't decode as version 1, nor the reverse
+        let data = account_data(&v0, BondingCurveV0::LEN);
+        assert!(LegacyBondingCurve::try_from_account_data(&data).is_err());
+        let data = account_data(&v0.clone().into_v1(), LegacyBondingCurve::LEN);
+        assert!(BondingCurveV0::try_from_account_data(&data).is_err());
+
+        // A version 1 sized account must say it is version 1
+        let mut v1 = v0.into_v1();
+        v1.version = BondingCurve::VERSION;
+        let data = account_data(&v1, LegacyBondingCurve::LEN);
+        let error = LegacyBondingCurve::try_from_account_data(&data).unwrap_err();
+        assert_eq!(error, PumpCloneError::InvalidAccount.into());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::{
+    curve::CurveReserves,
+    error::PumpCloneError,
+    events::{BondingCurveCompleted, KingOfTheHillChanged, EVENT_VERSION},
+    oracle::lamports_to_usd,
+    state::{BondingCurve, GlobalState, KingOfTheHill, PriceHistory, TokenMetadata},
+};
+
+/// Leaf committed to by a launch allowlist: keccak256(wallet).
+pub fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[wallet.as_ref()]).0
+}
+
+/// Leaf committed to by a presale tree: keccak256(wallet || allocation_le).
+pub fn presale_leaf(wallet: &Pubkey, allocation: u64) -> [u8; 32] {
+    keccak::hashv(&[wallet.as_ref(), &allocation.to_le_bytes()]).0
+}
+
+/// Verifies a keccak256 Merkle proof. Pairs are hashed in sorted order so
+/// proofs don't need to carry left/right position flags.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        }
+    });
+
+    computed == root
+}
+
+/// A wallet is blocked when its `BlockedWallet` PDA has been initialized.
+/// The PDA's seeds are checked by the instruction's account constraints, so
+/// a caller can't dodge the check by passing some other empty account.
+pub fn require_not_blocked(blocked_wallet: &AccountInfo) -> Result<()> {
+    require!(
+        blocked_wallet.data_is_empty(),
+        PumpCloneError::WalletBlacklisted
+    );
+
+    Ok(())
+}
+
+/// Deserializes a program-owned account after checking its owner; the
+/// discriminator is checked by `try_deserialize`.
+pub fn load_account<T: AccountDeserialize>(info: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*info.owner, crate::ID, PumpCloneError::InvalidAccount);
+    T::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// Closes a program-owned account the way `close` does, returning its rent
+/// to `destination`.
+pub fn close_account<'info>(info: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(info.lamports())
+        .ok_or(PumpCloneError::ArithmeticOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.assign(&anchor_lang::system_program::ID);
+    info.realloc(0, false).map_err(Into::into)
+}
+
+/// Events raised by `track_market_cap`, returned for the caller to emit
+/// through its event CPI.
+pub struct MarketCapEvents {
+    pub completed: Option<BondingCurveCompleted>,
+    pub king_changed: Option<KingOfTheHillChanged>,
+}
+
+/// Writes the post-trade market cap to the token's metadata and, once the
+/// token's USD market cap is past the king-of-the-hill threshold, stamps the
+/// time it got there and competes for the global crown. Reaching the USD
+/// graduation market cap completes the curve. Without a SOL/USD price only
+/// the market cap is written; the curve can still be completed later through
+/// `complete_bonding_curve`.
+pub fn track_market_cap(
+    bonding_curve: &mut BondingCurve,
+    token_metadata: &mut TokenMetadata,
+    king_of_the_hill: &mut KingOfTheHill,
+    king_of_the_hill_bump: u8,
+    global_state: &GlobalState,
+    sol_usd_price: Option<u64>,
+    timestamp: i64,
+) -> Result<MarketCapEvents> {
+    let market_cap = bonding_curve.market_cap()?;
+    token_metadata.market_cap = market_cap;
+    king_of_the_hill.bump = king_of_the_hill_bump;
+
+    let mut events = MarketCapEvents {
+        completed: None,
+        king_changed: None,
+    };
+    let Some(sol_usd_price) = sol_usd_price else {
+        return Ok(events);
+    };
+
+    let market_cap_usd = lamports_to_usd(market_cap, sol_usd_price)?;
+    if !bonding_curve.is_complete() && market_cap_usd >= global_state.graduation_market_cap_usd {
+        bonding_curve.set_complete();
+        events.completed = Some(BondingCurveCompleted {
+            version: EVENT_VERSION,
+            mint: bonding_curve.mint,
+            reserves: CurveReserves::from(&*bonding_curve),
+            sol_usd_price,
+            timestamp,
+        });
+    }
+
+    if market_cap_usd < global_state.king_of_hill_threshold() {
+        // A king that falls back below the threshold keeps its crown until
+        // another qualifying token overtakes the stored market cap
+        if king_of_the_hill.mint == token_metadata.mint {
+            king_of_the_hill.market_cap_usd = market_cap_usd;
+        }
+        return Ok(events);
+    }
+
+    if token_metadata.king_of_hill_timestamp == 0 {
+        token_metadata.king_of_hill_timestamp = timestamp;
+    }
+
+    let previous_king = king_of_the_hill.mint;
+    if king_of_the_hill.update(token_metadata.mint, market_cap_usd, timestamp) {
+        events.king_changed = Some(KingOfTheHillChanged {
+            version: EVENT_VERSION,
+            mint: token_metadata.mint,
+            previous_mint: previous_king,
+            market_cap_usd,
+            timestamp,
+        });
+    }
+
+    Ok(events)
+}
+
+/// Appends the post-trade spot price and the trade's SOL volume to the
+/// mint's candle history.
+pub fn record_price(
+    price_history: &AccountLoader<PriceHistory>,
+    bonding_curve: &BondingCurve,
+    sol_volume: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let price = bonding_curve.spot_price()?;
+    price_history.load_mut()?.record(timestamp, price, sol_volume)
+}
\ No newline at end of file
@@ -0,0 +1,248 @@
+//! Compute-unit benchmarks for every trading and admin instruction, checked
+//! against `tests/compute_units.baseline`.
+//!
+//! Compute units are only metered for the SBF build, so the benchmarks only
+//! build with the `test-sbf` feature and run with:
+//!
+//!     cargo test-sbf --features mock-oracle,test-sbf --test benchmarks -- --nocapture
+//!
+//! The test fails when any instruction uses more than the baseline plus
+//! `CU_REGRESSION_THRESHOLD_PCT` percent (5 by default), or has no baseline
+//! entry. After an intended change, rerun with `UPDATE_CU_BASELINE=1` and
+//! commit the new baseline; entries the benchmarks don't measure are kept.
+//! CI runs the benchmarks on every push (`.github/workflows/program.yml`).
+
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+use common::*;
+use pump_clone::{oracle::USD_SCALE, state::AdminAction};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+const DEFAULT_THRESHOLD_PCT: u64 = 5;
+
+const BUY_AMOUNT: u64 = 1_000_000_000;
+const FEES_COLLECTED: u64 = 500_000_000;
+
+/// A SOL price at which even a fresh curve is past the graduation market
+/// cap.
+const GRADUATION_SOL_USD_PRICE: u64 = 10_000 * USD_SCALE;
+
+#[tokio::test]
+async fn compute_units_match_baseline() {
+    assert!(running_sbf(), "compute units are only metered for the SBF build; run under cargo test-sbf");
+
+    let mut measured = BTreeMap::new();
+    measured.insert("create_token", create_token().await);
+    measured.insert("buy_tokens", buy_tokens().await);
+    measured.insert("sell_tokens", sell_tokens().await);
+    measured.insert("complete_bonding_curve", complete_bonding_curve().await);
+    measured.insert("withdraw", withdraw().await);
+    measured.insert("withdraw_fees", withdraw_fees().await);
+
+    let path = baseline_path();
+    let contents = fs::read_to_string(&path).unwrap_or_default();
+    let mut baseline = parse_baseline(&contents);
+
+    if env::var_os("UPDATE_CU_BASELINE").is_some() {
+        for (name, units) in &measured {
+            baseline.insert(name.to_string(), *units);
+        }
+        fs::write(&path, render_baseline(&contents, &baseline)).unwrap();
+        println!("wrote {}", path.display());
+        return;
+    }
+
+    let threshold_pct = env::var("CU_REGRESSION_THRESHOLD_PCT")
+        .map(|value| value.parse().expect("CU_REGRESSION_THRESHOLD_PCT is a whole percentage"))
+        .unwrap_or(DEFAULT_THRESHOLD_PCT);
+
+    let mut failures = Vec::new();
+    for (name, units) in &measured {
+        match baseline.get(*name) {
+            Some(&expected) => {
+                let limit = expected + expected * threshold_pct / 100;
+                println!("{name:<24} {units:>8} CU (baseline {expected}, limit {limit})");
+                if *units > limit {
+                    failures.push(format!("{name}: {units} CU > {limit} CU"));
+                }
+            }
+            None => {
+                println!("{name:<24} {units:>8} CU (no baseline)");
+                failures.push(format!("{name}: no baseline entry"));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "compute units over baseline + {threshold_pct}% or unrecorded:\n{}\nrerun with UPDATE_CU_BASELINE=1 if intended",
+        failures.join("\n")
+    );
+}
+
+async fn create_token() -> u64 {
+    let launch = Launch::new();
+    let mut program_test = bare_program_test(&launch);
+    add_platform(&mut program_test, &launch);
+    let mut context = program_test.start_with_context().await;
+
+    let mint = Keypair::new();
+    execute(
+        &mut context,
+        &[create_token_ix(&launch, &mint.pubkey(), create_token_params())],
+        &launch.creator,
+        &[&mint],
+    )
+    .await
+    .unwrap()
+}
+
+async fn buy_tokens() -> u64 {
+    let launch = Launch::new();
+    let buyer = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(buyer.pubkey(), system_account(10_000_000_000));
+    let mut context = program_test.start_with_context().await;
+
+    execute(&mut context, &[buy_ix(&launch, &buyer.pubkey(), BUY_AMOUNT, 0)], &buyer, &[])
+        .await
+        .unwrap()
+}
+
+async fn sell_tokens() -> u64 {
+    let launch = Launch::new();
+    let seller = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(seller.pubkey(), system_account(10_000_000_000));
+    let mut context = program_test.start_with_context().await;
+
+    execute(&mut context, &[buy_ix(&launch, &seller.pubkey(), BUY_AMOUNT, 0)], &seller, &[])
+        .await
+        .unwrap();
+    let balance = token_balance(&mut context, launch.token_account(&seller.pubkey())).await;
+
+    execute(&mut context, &[sell_ix(&launch, &seller.pubkey(), balance / 2, 0)], &seller, &[])
+        .await
+        .unwrap()
+}
+
+async fn complete_bonding_curve() -> u64 {
+    let launch = Launch::new();
+    let mut context = program_test(&launch).start_with_context().await;
+    set_sol_usd_price(&mut context, &launch, GRADUATION_SOL_USD_PRICE).await;
+
+    let authority = launch.authority.pubkey();
+    execute(
+        &mut context,
+        &[complete_bonding_curve_ix(&launch, &authority)],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap()
+}
+
+async fn withdraw() -> u64 {
+    let launch = Launch::new();
+    let program_test = program_test_with_curve(&launch, |curve| {
+        curve.real_sol_reserves = BUY_AMOUNT;
+        curve.complete = 1;
+    });
+    let mut context = program_test.start_with_context().await;
+
+    let authority = launch.authority.pubkey();
+    execute(&mut context, &[withdraw_ix(&launch, &authority)], &launch.authority, &[])
+        .await
+        .unwrap()
+}
+
+/// Fees are withdrawn through the admin multisig, so this measures the
+/// `execute_admin_proposal` that pays them out.
+async fn withdraw_fees() -> u64 {
+    let launch = Launch::new();
+    let mut context = program_test(&launch).start_with_context().await;
+    let authority = launch.authority.pubkey();
+    let multisig = admin_multisig();
+
+    execute(
+        &mut context,
+        &[
+            system_instruction::transfer(&authority, &launch.fee_vault, FEES_COLLECTED),
+            create_admin_multisig_ix(&launch, vec![authority], 1),
+            propose_authority_ix(&launch, &authority, multisig),
+            create_admin_proposal_ix(&authority, 0, AdminAction::AcceptAuthority),
+            execute_admin_proposal_ix(&launch, &authority, 0, authority),
+            create_admin_proposal_ix(
+                &authority,
+                1,
+                AdminAction::WithdrawFees {
+                    amount: FEES_COLLECTED,
+                    recipient: authority,
+                },
+            ),
+        ],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    execute(
+        &mut context,
+        &[execute_admin_proposal_ix(&launch, &authority, 1, authority)],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap()
+}
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compute_units.baseline")
+}
+
+/// One `name units` pair per line; `#` starts a comment.
+fn parse_baseline(contents: &str) -> BTreeMap<String, u64> {
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, units) = line
+                .split_once(char::is_whitespace)
+                .unwrap_or_else(|| panic!("malformed baseline line: {line}"));
+            let units = units
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("malformed baseline line: {line}"));
+            (name.to_string(), units)
+        })
+        .collect()
+}
+
+/// Rewrites the baseline with `baseline`'s entries, keeping the comment
+/// lines of `previous`.
+fn render_baseline(previous: &str, baseline: &BTreeMap<String, u64>) -> String {
+    let mut contents: String = previous
+        .lines()
+        .filter(|line| line.trim_start().starts_with('#'))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    if contents.is_empty() {
+        contents.push_str(
+            "# Compute units per instruction, written by the `benchmarks` test.\n\
+             # Regenerate with UPDATE_CU_BASELINE=1 after an intended change.\n",
+        );
+    }
+    for (name, units) in baseline {
+        contents.push_str(&format!("{name} {units}\n"));
+    }
+    contents
+}
\ No newline at end of file
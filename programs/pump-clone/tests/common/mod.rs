@@ -0,0 +1,1280 @@
+//! Shared fixtures for the program tests. Accounts are written straight into
+//! the test bank so each test starts from a known curve without replaying
+//! the instructions that would normally create it.
+//!
+//! Under `cargo test` the program runs natively through `processor!`. Under
+//! `cargo test-sbf` program-test loads its SBF build from `SBF_OUT_DIR`
+//! instead. Metaplex Token Metadata always runs from the SBF build in
+//! `tests/fixtures/mpl_token_metadata.so` when it has been committed (see
+//! `tests/fixtures/README.md`); a plain `cargo test` without it falls back to
+//! `metaplex_stand_in`. Compute units are only metered for SBF programs.
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult, program::invoke_signed, program_error::ProgramError,
+        program_pack::Pack, system_instruction,
+    },
+    AccountDeserialize, AccountSerialize, Discriminator, InstructionData, ToAccountMetas,
+    ZeroCopy,
+};
+use mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs;
+use pump_clone::{
+    instructions::{create_token::CreateTokenParams, initialize_platform::InitializePlatformParams},
+    oracle::{MockPriceFeed, USD_SCALE},
+    state::{
+        AdminAction, BondingCurve, Candle, ConfigUpdate, GlobalState, PriceHistory,
+        TokenMetadata, VersionedAccount,
+    },
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{Instruction, InstructionError},
+    program_option::COption,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+pub const TOKEN_DECIMALS: u8 = 6;
+pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+pub const INITIAL_VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+pub const PLATFORM_FEE_BPS: u16 = 100;
+pub const MIGRATION_FEE: u64 = 100_000_000;
+pub const SOL_USD_PRICE: u64 = 150 * USD_SCALE;
+pub const GRADUATION_MARKET_CAP_USD: u64 = 69_000 * USD_SCALE;
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &pump_clone::ID).0
+}
+
+/// Native entrypoint for `processor!`. Anchor's `entry` wants the account
+/// slice to live as long as the accounts it holds, which `processor!`'s
+/// signature doesn't promise.
+fn process_instruction<'info>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    data: &[u8],
+) -> ProgramResult {
+    // SAFETY: only the lifetime is widened. `entry` uses the slice for the
+    // length of this call, during which the runtime keeps it alive, and
+    // nothing it returns borrows from it.
+    let accounts: &'info [AccountInfo<'info>] =
+        unsafe { &*(accounts as *const [AccountInfo<'info>]) };
+    pump_clone::entry(program_id, accounts, data)
+}
+
+/// `CreateMetadataAccountV3`, the only Metaplex instruction the program
+/// invokes.
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+/// Native stand-in for Metaplex Token Metadata, used when its SBF build
+/// hasn't been committed to `tests/fixtures`; Metaplex's Rust crate can't be
+/// built against this Solana version. It accepts only
+/// `CreateMetadataAccountV3`, checks the metadata PDA and that the mint
+/// authority signed, and creates the metadata account holding the raw
+/// instruction arguments. Metaplex's own validation and layout are only
+/// checked against the fixture.
+fn metaplex_stand_in(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let Some((&CREATE_METADATA_ACCOUNT_V3, args)) = data.split_first() else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let [metadata, mint, mint_authority, payer, _update_authority, system_program, ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let seeds: &[&[u8]] = &[b"metadata", program_id.as_ref(), mint.key.as_ref()];
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    if *metadata.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mint_state = spl_token::state::Mint::unpack(&mint.try_borrow_data()?)?;
+    if !mint_authority.is_signer || mint_state.mint_authority != COption::Some(*mint_authority.key)
+    {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            metadata.key,
+            Rent::get()?.minimum_balance(args.len()),
+            args.len() as u64,
+            program_id,
+        ),
+        &[payer.clone(), metadata.clone(), system_program.clone()],
+        &[&[b"metadata", program_id.as_ref(), mint.key.as_ref(), &[bump]]],
+    )?;
+    metadata.try_borrow_mut_data()?.copy_from_slice(args);
+
+    Ok(())
+}
+
+/// Whether program-test runs the real Metaplex program from
+/// `tests/fixtures` rather than `metaplex_stand_in`.
+pub fn metaplex_fixture_loaded() -> bool {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/mpl_token_metadata.so")
+        .exists()
+}
+
+/// Name, symbol and URI held by a metadata account, as Metaplex lays it out
+/// or as `metaplex_stand_in` does.
+pub fn metadata_name_symbol_uri(data: &[u8]) -> (String, String, String) {
+    if metaplex_fixture_loaded() {
+        let metadata = mpl_token_metadata::accounts::Metadata::from_bytes(data).unwrap();
+        // Metaplex pads each string with NULs to its maximum length
+        let trim = |value: String| value.trim_end_matches('\0').to_string();
+        (trim(metadata.name), trim(metadata.symbol), trim(metadata.uri))
+    } else {
+        let args = CreateMetadataAccountV3InstructionArgs::deserialize(&mut &data[..]).unwrap();
+        (args.data.name, args.data.symbol, args.data.uri)
+    }
+}
+
+pub fn metaplex_metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+/// Addresses of everything a single launch touches.
+pub struct Launch {
+    pub authority: Keypair,
+    pub creator: Keypair,
+    pub mint: Pubkey,
+    pub global_state: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub bonding_curve_token_account: Pubkey,
+    pub token_metadata: Pubkey,
+    pub price_history: Pubkey,
+    pub oracle_feed: Pubkey,
+    pub fee_vault: Pubkey,
+    pub king_of_the_hill: Pubkey,
+    pub event_authority: Pubkey,
+}
+
+impl Launch {
+    pub fn new() -> Self {
+        let mint = Pubkey::new_unique();
+        let bonding_curve = pda(&[b"bonding_curve", mint.as_ref()]);
+
+        Self {
+            authority: Keypair::new(),
+            creator: Keypair::new(),
+            mint,
+            global_state: pda(&[b"global_state"]),
+            bonding_curve,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, &mint),
+            token_metadata: pda(&[b"token_metadata", mint.as_ref()]),
+            price_history: pda(&[b"price_history", mint.as_ref()]),
+            oracle_feed: Pubkey::new_unique(),
+            fee_vault: pda(&[b"fee_vault"]),
+            king_of_the_hill: pda(&[b"king_of_the_hill"]),
+            event_authority: pda(&[b"__event_authority"]),
+        }
+    }
+
+    /// The same platform and signers, pointed at the launch for `mint`.
+    pub fn with_mint(&self, mint: Pubkey) -> Self {
+        let bonding_curve = pda(&[b"bonding_curve", mint.as_ref()]);
+
+        Self {
+            authority: self.authority.insecure_clone(),
+            creator: self.creator.insecure_clone(),
+            mint,
+            bonding_curve,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, &mint),
+            token_metadata: pda(&[b"token_metadata", mint.as_ref()]),
+            price_history: pda(&[b"price_history", mint.as_ref()]),
+            ..*self
+        }
+    }
+
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint)
+    }
+
+    pub fn user_stats(&self, user: &Pubkey) -> Pubkey {
+        pda(&[b"user_stats", user.as_ref()])
+    }
+
+    pub fn position(&self, user: &Pubkey) -> Pubkey {
+        pda(&[b"position", user.as_ref(), self.mint.as_ref()])
+    }
+
+    pub fn blocked_wallet(&self, wallet: &Pubkey) -> Pubkey {
+        pda(&[b"blocked_wallet", wallet.as_ref()])
+    }
+}
+
+/// The program, Metaplex, funded signers and the mock price feed, before
+/// `initialize_platform` has run.
+pub fn bare_program_test(launch: &Launch) -> ProgramTest {
+    let mut program_test =
+        ProgramTest::new("pump_clone", pump_clone::ID, processor!(process_instruction));
+    if metaplex_fixture_loaded() {
+        // Without a processor program-test loads the build from
+        // `tests/fixtures` and runs it in the SBF VM, natively too
+        program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+    } else {
+        program_test.add_program(
+            "mpl_token_metadata",
+            mpl_token_metadata::ID,
+            processor!(metaplex_stand_in),
+        );
+    }
+
+    program_test.add_account(launch.authority.pubkey(), system_account(100_000_000_000));
+    program_test.add_account(launch.creator.pubkey(), system_account(100_000_000_000));
+
+    add_anchor_account(
+        &mut program_test,
+        launch.oracle_feed,
+        &MockPriceFeed {
+            price: SOL_USD_PRICE,
+            confidence: 0,
+            updated_at: 0,
+        },
+        8 + std::mem::size_of::<MockPriceFeed>(),
+    );
+
+    program_test
+}
+
+/// An initialized platform with a launch already on its curve.
+pub fn program_test(launch: &Launch) -> ProgramTest {
+    program_test_with_curve(launch, |_| {})
+}
+
+/// Like `program_test`, with `configure` applied to the curve before it is
+/// written, e.g. to start from reserves close to graduation.
+pub fn program_test_with_curve(
+    launch: &Launch,
+    configure: impl FnOnce(&mut BondingCurve),
+) -> ProgramTest {
+    let mut program_test = bare_program_test(launch);
+    add_platform(&mut program_test, launch);
+    add_curve(&mut program_test, launch, configure);
+    program_test
+}
+
+fn bump(address: &Pubkey, seeds: &[&[u8]]) -> u8 {
+    let (expected, bump) = Pubkey::find_program_address(seeds, &pump_clone::ID);
+    assert_eq!(*address, expected);
+    bump
+}
+
+/// Writes the global state and fee vault `initialize_platform` would leave
+/// behind.
+pub fn add_platform(program_test: &mut ProgramTest, launch: &Launch) {
+    add_platform_with(program_test, launch, |_| {});
+}
+
+/// Like `add_platform`, with `configure` applied to the global state before
+/// it is written, e.g. to hand the authority to governance.
+pub fn add_platform_with(
+    program_test: &mut ProgramTest,
+    launch: &Launch,
+    configure: impl FnOnce(&mut GlobalState),
+) {
+    let mut global_state = GlobalState {
+        authority: launch.authority.pubkey(),
+        pending_authority: None,
+        guardian: launch.authority.pubkey(),
+        fee_recipient: launch.authority.pubkey(),
+        platform_fee_bps: PLATFORM_FEE_BPS,
+        creator_fee_bps: 0,
+        migration_fee: MIGRATION_FEE,
+        min_sol_threshold: 0,
+        max_sol_threshold: u64::MAX,
+        initial_virtual_token_reserves: INITIAL_VIRTUAL_TOKEN_RESERVES,
+        initial_virtual_sol_reserves: INITIAL_VIRTUAL_SOL_RESERVES,
+        initial_real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+        max_creator_allocation_bps: 0,
+        graduation_market_cap_usd: GRADUATION_MARKET_CAP_USD,
+        king_of_hill_bps: 5000,
+        oracle_feed: launch.oracle_feed,
+        oracle_max_staleness: i64::MAX,
+        oracle_max_confidence_bps: 10000,
+        token_expiry: 0,
+        config_timelock: 0,
+        config_change_count: 0,
+        pause_flags: 0,
+        bump: bump(&launch.global_state, &[b"global_state"]),
+        version: GlobalState::VERSION,
+        reserved: [0; 64],
+    };
+    configure(&mut global_state);
+
+    add_anchor_account(program_test, launch.global_state, &global_state, GlobalState::LEN);
+
+    program_test.add_account(launch.fee_vault, system_account(rent_exempt(0)));
+}
+
+/// Writes the accounts `create_token` would leave behind for `launch.mint`.
+pub fn add_curve(
+    program_test: &mut ProgramTest,
+    launch: &Launch,
+    configure: impl FnOnce(&mut BondingCurve),
+) {
+    let now = 0;
+    let mut curve = BondingCurve {
+        mint: launch.mint,
+        creator: launch.creator.pubkey(),
+        allowlist_root: [0; 32],
+        presale_merkle_root: [0; 32],
+        virtual_token_reserves: INITIAL_VIRTUAL_TOKEN_RESERVES,
+        virtual_sol_reserves: INITIAL_VIRTUAL_SOL_RESERVES,
+        real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+        real_sol_reserves: 0,
+        token_total_supply: TOTAL_SUPPLY,
+        created_at: now,
+        presale_allocation: 0,
+        presale_tokens_sold: 0,
+        presale_sol_raised: 0,
+        presale_ends_at: 0,
+        last_price_update: now,
+        trade_count: 0,
+        deadline: 0,
+        initial_real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+        refund_sol_pool: 0,
+        refund_token_supply: 0,
+        tokens_refunded: 0,
+        price_cumulative: [0; 16],
+        complete: 0,
+        presale_finalized: 0,
+        bump: bump(&launch.bonding_curve, &[b"bonding_curve", launch.mint.as_ref()]),
+        version: BondingCurve::VERSION,
+        _padding: [0; 4],
+        reserved: [0; 64],
+    };
+    configure(&mut curve);
+
+    // The curve holds the SOL it has raised on top of its rent
+    let mut account = program_account(zero_copy_data(&curve, BondingCurve::LEN));
+    account.lamports += curve.real_sol_reserves;
+    program_test.add_account(launch.bonding_curve, account);
+
+    add_anchor_account(
+        program_test,
+        launch.token_metadata,
+        &TokenMetadata {
+            mint: launch.mint,
+            creator: launch.creator.pubkey(),
+            name: "Bench".to_string(),
+            symbol: "BNCH".to_string(),
+            description: String::new(),
+            image_uri: String::new(),
+            metadata_uri: String::new(),
+            twitter: String::new(),
+            telegram: String::new(),
+            website: String::new(),
+            created_at: now,
+            market_cap: 0,
+            reply_count: 0,
+            king_of_hill_timestamp: 0,
+            nsfw: false,
+            show_name: true,
+            bump: bump(&launch.token_metadata, &[b"token_metadata", launch.mint.as_ref()]),
+            version: TokenMetadata::VERSION,
+            reserved: [0; 64],
+        },
+        TokenMetadata::LEN,
+    );
+
+    add_zero_copy_account(
+        program_test,
+        launch.price_history,
+        &PriceHistory {
+            mint: launch.mint,
+            head: 0,
+            len: 0,
+            bump: bump(&launch.price_history, &[b"price_history", launch.mint.as_ref()]),
+            _padding: [0; 7],
+            candles: [Candle::default(); PriceHistory::MAX_CANDLES],
+        },
+        PriceHistory::LEN,
+    );
+
+    program_test.add_account(
+        launch.mint,
+        packed_account(
+            spl_token::state::Mint {
+                mint_authority: COption::Some(launch.bonding_curve),
+                supply: INITIAL_REAL_TOKEN_RESERVES,
+                decimals: TOKEN_DECIMALS,
+                is_initialized: true,
+                freeze_authority: COption::Some(launch.bonding_curve),
+            },
+            spl_token::ID,
+        ),
+    );
+
+    program_test.add_account(
+        launch.bonding_curve_token_account,
+        token_account(launch.mint, launch.bonding_curve, curve.real_token_reserves),
+    );
+}
+
+pub fn rent_exempt(space: usize) -> u64 {
+    Rent::default().minimum_balance(space)
+}
+
+pub fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &solana_sdk::system_program::ID)
+}
+
+pub fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    packed_account(
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        spl_token::ID,
+    )
+}
+
+pub fn add_anchor_account<T: AccountSerialize>(
+    program_test: &mut ProgramTest,
+    address: Pubkey,
+    account: &T,
+    space: usize,
+) {
+    let mut data = Vec::with_capacity(space);
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+
+    program_test.add_account(address, program_account(data));
+}
+
+pub fn add_zero_copy_account<T: ZeroCopy + Discriminator>(
+    program_test: &mut ProgramTest,
+    address: Pubkey,
+    account: &T,
+    space: usize,
+) {
+    program_test.add_account(address, program_account(zero_copy_data(account, space)));
+}
+
+fn zero_copy_data<T: ZeroCopy + Discriminator>(account: &T, space: usize) -> Vec<u8> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    data.resize(space, 0);
+    data
+}
+
+pub fn program_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: rent_exempt(data.len()),
+        data,
+        owner: pump_clone::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn packed_account<T: Pack>(state: T, owner: Pubkey) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+
+    Account {
+        lamports: rent_exempt(T::LEN),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// The same configuration `add_platform` writes.
+pub fn initialize_platform_params(launch: &Launch) -> InitializePlatformParams {
+    InitializePlatformParams {
+        guardian: launch.authority.pubkey(),
+        fee_recipient: launch.authority.pubkey(),
+        platform_fee_bps: PLATFORM_FEE_BPS,
+        creator_fee_bps: 0,
+        migration_fee: MIGRATION_FEE,
+        min_sol_threshold: 0,
+        max_sol_threshold: u64::MAX,
+        initial_virtual_token_reserves: INITIAL_VIRTUAL_TOKEN_RESERVES,
+        initial_virtual_sol_reserves: INITIAL_VIRTUAL_SOL_RESERVES,
+        initial_real_token_reserves: INITIAL_REAL_TOKEN_RESERVES,
+        max_creator_allocation_bps: 0,
+        graduation_market_cap_usd: GRADUATION_MARKET_CAP_USD,
+        king_of_hill_bps: 5000,
+        oracle_feed: launch.oracle_feed,
+        oracle_max_staleness: i64::MAX,
+        oracle_max_confidence_bps: 10000,
+        token_expiry: 0,
+        config_timelock: 0,
+    }
+}
+
+pub fn initialize_platform_ix(launch: &Launch) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::InitializePlatform {
+            authority: launch.authority.pubkey(),
+            global_state: launch.global_state,
+            fee_vault: launch.fee_vault,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::InitializePlatform {
+            params: initialize_platform_params(launch),
+        }
+        .data(),
+    }
+}
+
+pub fn create_token_params() -> CreateTokenParams {
+    CreateTokenParams {
+        name: "Bench".to_string(),
+        symbol: "BNCH".to_string(),
+        uri: "https://example.com/bench.json".to_string(),
+        description: String::new(),
+        image_uri: String::new(),
+        twitter: String::new(),
+        telegram: String::new(),
+        website: String::new(),
+        nsfw: false,
+        show_name: true,
+        allowlist_root: None,
+        presale: None,
+        creator_allocation: None,
+        deadline_duration: None,
+    }
+}
+
+/// Creates a new launch for `mint`, which must sign as it is initialized.
+pub fn create_token_ix(launch: &Launch, mint: &Pubkey, params: CreateTokenParams) -> Instruction {
+    let launch = launch.with_mint(*mint);
+    let creator = launch.creator.pubkey();
+
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CreateToken {
+            creator,
+            global_state: launch.global_state,
+            mint: *mint,
+            bonding_curve: launch.bonding_curve,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            token_metadata: launch.token_metadata,
+            price_history: launch.price_history,
+            user_stats: launch.user_stats(&creator),
+            vesting_escrow: None,
+            vesting_token_account: None,
+            metadata: metaplex_metadata(mint),
+            blocked_wallet: launch.blocked_wallet(&creator),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_sdk::system_program::ID,
+            rent: solana_sdk::sysvar::rent::ID,
+            event_authority: launch.event_authority,
+            program: pump_clone::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CreateToken { params }.data(),
+    }
+}
+
+pub fn buy_ix(launch: &Launch, buyer: &Pubkey, sol_amount: u64, min_tokens_out: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::BuyTokens {
+            buyer: *buyer,
+            global_state: launch.global_state,
+            bonding_curve: launch.bonding_curve,
+            mint: launch.mint,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            buyer_token_account: launch.token_account(buyer),
+            token_metadata: launch.token_metadata,
+            king_of_the_hill: launch.king_of_the_hill,
+            user_stats: launch.user_stats(buyer),
+            position: launch.position(buyer),
+            price_history: launch.price_history,
+            oracle_feed: launch.oracle_feed,
+            blocked_wallet: launch.blocked_wallet(buyer),
+            fee_vault: launch.fee_vault,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::ID,
+            event_authority: launch.event_authority,
+            program: pump_clone::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::BuyTokens {
+            sol_amount,
+            min_tokens_out,
+            allowlist_proof: vec![],
+        }
+        .data(),
+    }
+}
+
+pub fn sell_ix(launch: &Launch, seller: &Pubkey, token_amount: u64, min_sol_out: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::SellTokens {
+            seller: *seller,
+            global_state: launch.global_state,
+            bonding_curve: launch.bonding_curve,
+            mint: launch.mint,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            seller_token_account: launch.token_account(seller),
+            token_metadata: launch.token_metadata,
+            king_of_the_hill: launch.king_of_the_hill,
+            user_stats: launch.user_stats(seller),
+            position: launch.position(seller),
+            price_history: launch.price_history,
+            oracle_feed: launch.oracle_feed,
+            blocked_wallet: launch.blocked_wallet(seller),
+            fee_vault: launch.fee_vault,
+            token_program: spl_token::ID,
+            system_program: solana_sdk::system_program::ID,
+            event_authority: launch.event_authority,
+            program: pump_clone::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::SellTokens {
+            token_amount,
+            min_sol_out,
+        }
+        .data(),
+    }
+}
+
+pub fn complete_bonding_curve_ix(launch: &Launch, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CompleteBondingCurve {
+            authority: *authority,
+            global_state: launch.global_state,
+            bonding_curve: launch.bonding_curve,
+            oracle_feed: launch.oracle_feed,
+            event_authority: launch.event_authority,
+            program: pump_clone::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CompleteBondingCurve {}.data(),
+    }
+}
+
+pub fn withdraw_ix(launch: &Launch, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::Withdraw {
+            authority: *authority,
+            global_state: launch.global_state,
+            bonding_curve: launch.bonding_curve,
+            mint: launch.mint,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            authority_token_account: launch.token_account(authority),
+            fee_vault: launch.fee_vault,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::ID,
+            event_authority: launch.event_authority,
+            program: pump_clone::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::Withdraw {}.data(),
+    }
+}
+
+pub fn pause_ix(launch: &Launch, pauser: &Pubkey, scopes: u8) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::Pause {
+            pauser: *pauser,
+            global_state: launch.global_state,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::Pause { scopes }.data(),
+    }
+}
+
+pub fn unpause_ix(launch: &Launch, authority: &Pubkey, scopes: u8) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::Unpause {
+            authority: *authority,
+            global_state: launch.global_state,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::Unpause { scopes }.data(),
+    }
+}
+
+pub fn block_wallet_ix(launch: &Launch, wallet: Pubkey, reason: &str) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::BlockWallet {
+            authority: launch.authority.pubkey(),
+            global_state: launch.global_state,
+            blocked_wallet: launch.blocked_wallet(&wallet),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::BlockWallet {
+            wallet,
+            reason: reason.to_string(),
+        }
+        .data(),
+    }
+}
+
+pub fn unblock_wallet_ix(launch: &Launch, wallet: Pubkey) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::UnblockWallet {
+            authority: launch.authority.pubkey(),
+            global_state: launch.global_state,
+            blocked_wallet: launch.blocked_wallet(&wallet),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::UnblockWallet { wallet }.data(),
+    }
+}
+
+pub fn quote_buy_ix(launch: &Launch, sol_amount: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CurveView {
+            global_state: launch.global_state,
+            bonding_curve: launch.bonding_curve,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::QuoteBuy { sol_amount }.data(),
+    }
+}
+
+pub fn propose_authority_ix(
+    launch: &Launch,
+    authority: &Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ProposeAuthority {
+            authority: *authority,
+            global_state: launch.global_state,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ProposeAuthority { new_authority }.data(),
+    }
+}
+
+pub fn upgrade_account_ix(
+    launch: &Launch,
+    authority: &Pubkey,
+    target: Pubkey,
+    kind: VersionedAccount,
+) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::UpgradeAccount {
+            authority: *authority,
+            global_state: launch.global_state,
+            target,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::UpgradeAccount { kind }.data(),
+    }
+}
+
+pub fn presale_buy_ix(
+    launch: &Launch,
+    buyer: &Pubkey,
+    token_amount: u64,
+    max_sol_cost: u64,
+    allocation: u64,
+) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::PresaleBuy {
+            buyer: *buyer,
+            global_state: launch.global_state,
+            bonding_curve: launch.bonding_curve,
+            mint: launch.mint,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            buyer_token_account: launch.token_account(buyer),
+            presale_receipt: pda(&[b"presale_receipt", launch.mint.as_ref(), buyer.as_ref()]),
+            user_stats: launch.user_stats(buyer),
+            position: launch.position(buyer),
+            blocked_wallet: launch.blocked_wallet(buyer),
+            fee_vault: launch.fee_vault,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        // A single-wallet allowlist: the root is the wallet's own leaf
+        data: pump_clone::instruction::PresaleBuy {
+            token_amount,
+            max_sol_cost,
+            allocation,
+            proof: vec![],
+        }
+        .data(),
+    }
+}
+
+pub fn finalize_presale_ix(launch: &Launch) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::FinalizePresale {
+            bonding_curve: launch.bonding_curve,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::FinalizePresale {}.data(),
+    }
+}
+
+pub fn vesting_escrow(launch: &Launch) -> Pubkey {
+    pda(&[b"vesting_escrow", launch.mint.as_ref()])
+}
+
+pub fn claim_vested_ix(launch: &Launch, beneficiary: &Pubkey) -> Instruction {
+    let vesting_escrow = vesting_escrow(launch);
+
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ClaimVested {
+            beneficiary: *beneficiary,
+            mint: launch.mint,
+            vesting_escrow,
+            vesting_token_account: get_associated_token_address(&vesting_escrow, &launch.mint),
+            beneficiary_token_account: launch.token_account(beneficiary),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ClaimVested {}.data(),
+    }
+}
+
+pub fn refund_ix(launch: &Launch, user: &Pubkey, token_amount: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::Refund {
+            user: *user,
+            mint: launch.mint,
+            bonding_curve: launch.bonding_curve,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            user_token_account: launch.token_account(user),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::ID,
+            event_authority: launch.event_authority,
+            program: pump_clone::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::Refund { token_amount }.data(),
+    }
+}
+
+pub fn close_token_ix(launch: &Launch) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CloseToken {
+            global_state: launch.global_state,
+            creator: launch.creator.pubkey(),
+            mint: launch.mint,
+            bonding_curve: launch.bonding_curve,
+            bonding_curve_token_account: launch.bonding_curve_token_account,
+            token_metadata: launch.token_metadata,
+            price_history: launch.price_history,
+            fee_vault: launch.fee_vault,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CloseToken {}.data(),
+    }
+}
+
+pub fn config_change(index: u64) -> Pubkey {
+    pda(&[b"config_change", &index.to_le_bytes()])
+}
+
+pub fn queue_config_change_ix(launch: &Launch, index: u64, update: ConfigUpdate) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::QueueConfigChange {
+            authority: launch.authority.pubkey(),
+            global_state: launch.global_state,
+            pending_config_change: config_change(index),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::QueueConfigChange { update }.data(),
+    }
+}
+
+pub fn execute_config_change_ix(launch: &Launch, index: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ExecuteConfigChange {
+            global_state: launch.global_state,
+            pending_config_change: config_change(index),
+            proposer: launch.authority.pubkey(),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ExecuteConfigChange {}.data(),
+    }
+}
+
+pub fn governance() -> Pubkey {
+    pda(&[b"governance"])
+}
+
+pub fn voter_deposit(voter: &Pubkey) -> Pubkey {
+    pda(&[b"voter_deposit", voter.as_ref()])
+}
+
+pub fn governance_proposal(index: u64) -> Pubkey {
+    pda(&[b"governance_proposal", &index.to_le_bytes()])
+}
+
+pub fn deposit_votes_ix(governance_mint: &Pubkey, voter: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::DepositVotes {
+            voter: *voter,
+            governance: governance(),
+            governance_mint: *governance_mint,
+            governance_vault: get_associated_token_address(&governance(), governance_mint),
+            voter_token_account: get_associated_token_address(voter, governance_mint),
+            voter_deposit: voter_deposit(voter),
+            token_program: spl_token::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::DepositVotes { amount }.data(),
+    }
+}
+
+pub fn create_proposal_ix(proposer: &Pubkey, index: u64, action: AdminAction) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CreateProposal {
+            proposer: *proposer,
+            governance: governance(),
+            voter_deposit: voter_deposit(proposer),
+            proposal: governance_proposal(index),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CreateProposal { action }.data(),
+    }
+}
+
+pub fn cast_vote_ix(voter: &Pubkey, index: u64, support: bool) -> Instruction {
+    let proposal = governance_proposal(index);
+
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CastVote {
+            voter: *voter,
+            proposal,
+            voter_deposit: voter_deposit(voter),
+            vote_record: pda(&[b"vote_record", proposal.as_ref(), voter.as_ref()]),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CastVote { support }.data(),
+    }
+}
+
+/// Executes a governance proposal whose action needs no target account.
+pub fn execute_proposal_ix(launch: &Launch, executor: &Pubkey, index: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ExecuteProposal {
+            executor: *executor,
+            governance: governance(),
+            proposal: governance_proposal(index),
+            global_state: launch.global_state,
+            fee_vault: launch.fee_vault,
+            recipient: *executor,
+            target: None,
+            oracle_feed: None,
+            bonding_curve_token_account: None,
+            recipient_token_account: None,
+            token_program: None,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ExecuteProposal {}.data(),
+    }
+}
+
+pub fn admin_multisig() -> Pubkey {
+    pda(&[b"admin_multisig"])
+}
+
+pub fn admin_proposal(index: u64) -> Pubkey {
+    pda(&[b"admin_proposal", admin_multisig().as_ref(), &index.to_le_bytes()])
+}
+
+pub fn create_admin_multisig_ix(
+    launch: &Launch,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CreateAdminMultisig {
+            authority: launch.authority.pubkey(),
+            global_state: launch.global_state,
+            admin_multisig: admin_multisig(),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CreateAdminMultisig { signers, threshold }.data(),
+    }
+}
+
+pub fn create_admin_proposal_ix(proposer: &Pubkey, index: u64, action: AdminAction) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::CreateAdminProposal {
+            proposer: *proposer,
+            admin_multisig: admin_multisig(),
+            admin_proposal: admin_proposal(index),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::CreateAdminProposal { action }.data(),
+    }
+}
+
+pub fn approve_admin_proposal_ix(signer: &Pubkey, index: u64) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ApproveAdminProposal {
+            signer: *signer,
+            admin_multisig: admin_multisig(),
+            admin_proposal: admin_proposal(index),
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ApproveAdminProposal {}.data(),
+    }
+}
+
+pub fn execute_admin_proposal_ix(
+    launch: &Launch,
+    executor: &Pubkey,
+    index: u64,
+    recipient: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ExecuteAdminProposal {
+            executor: *executor,
+            admin_multisig: admin_multisig(),
+            admin_proposal: admin_proposal(index),
+            global_state: launch.global_state,
+            fee_vault: launch.fee_vault,
+            recipient,
+            target: None,
+            oracle_feed: None,
+            bonding_curve_token_account: None,
+            recipient_token_account: None,
+            token_program: None,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ExecuteAdminProposal {}.data(),
+    }
+}
+
+/// Executes a proposal acting on `launch`'s curve: `CompleteBondingCurve`,
+/// or `Withdraw` paying `recipient`.
+pub fn execute_curve_admin_proposal_ix(
+    launch: &Launch,
+    executor: &Pubkey,
+    index: u64,
+    recipient: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: pump_clone::ID,
+        accounts: pump_clone::accounts::ExecuteAdminProposal {
+            executor: *executor,
+            admin_multisig: admin_multisig(),
+            admin_proposal: admin_proposal(index),
+            global_state: launch.global_state,
+            fee_vault: launch.fee_vault,
+            recipient,
+            target: Some(launch.bonding_curve),
+            oracle_feed: Some(launch.oracle_feed),
+            bonding_curve_token_account: Some(launch.bonding_curve_token_account),
+            recipient_token_account: Some(launch.token_account(&recipient)),
+            token_program: Some(spl_token::ID),
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: pump_clone::instruction::ExecuteAdminProposal {}.data(),
+    }
+}
+
+/// Whether program-test is loading the SBF build of the program, the only
+/// one the runtime meters compute units for.
+pub fn running_sbf() -> bool {
+    std::env::var_os("SBF_OUT_DIR").is_some() || std::env::var_os("BPF_OUT_DIR").is_some()
+}
+
+/// Runs `instructions` in one transaction paid by `payer` and returns the
+/// compute units it consumed, or the transaction error.
+pub async fn execute(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> std::result::Result<u64, TransactionError> {
+    let mut all_instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
+    all_instructions.extend_from_slice(instructions);
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    // A fresh blockhash keeps identical transactions in one test from being
+    // rejected as duplicates
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &all_instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+
+    let outcome = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .map_err(|error| match error {
+            BanksClientError::TransactionError(error) => error,
+            error => panic!("banks client error: {error}"),
+        })?;
+
+    outcome.result?;
+
+    Ok(outcome
+        .metadata
+        .map(|metadata| metadata.compute_units_consumed)
+        .unwrap_or_default())
+}
+
+/// Fetches and decodes a zero-copy account.
+pub async fn load_zero_copy<T: ZeroCopy + Discriminator>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account exists");
+
+    assert_eq!(&account.data[..8], &T::DISCRIMINATOR[..]);
+    bytemuck::pod_read_unaligned(&account.data[8..8 + std::mem::size_of::<T>()])
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("token account exists");
+
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+/// Fetches and decodes an Anchor account.
+pub async fn load_anchor<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account exists");
+
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context.banks_client.get_balance(address).await.unwrap()
+}
+
+pub async fn unix_timestamp(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+/// Moves the clock the program sees to `unix_timestamp`.
+pub async fn set_unix_timestamp(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+/// Moves the mock SOL/USD price, as a feed update would.
+pub async fn set_sol_usd_price(context: &mut ProgramTestContext, launch: &Launch, price: u64) {
+    let mut account = context
+        .banks_client
+        .get_account(launch.oracle_feed)
+        .await
+        .unwrap()
+        .expect("oracle feed exists");
+
+    let mut feed = MockPriceFeed::try_deserialize(&mut account.data.as_slice()).unwrap();
+    feed.price = price;
+    account.data.clear();
+    feed.try_serialize(&mut account.data).unwrap();
+
+    context.set_account(&launch.oracle_feed, &account.into());
+}
+
+/// Asserts that a program instruction failed with the custom error `code`,
+/// e.g. `PumpCloneError::SlippageToleranceExceeded.into()`.
+pub fn assert_program_error(result: std::result::Result<u64, TransactionError>, code: u32) {
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(actual))) => {
+            assert_eq!(actual, code, "expected program error {code}, got {actual}")
+        }
+        other => panic!("expected program error {code}, got {other:?}"),
+    }
+}
\ No newline at end of file
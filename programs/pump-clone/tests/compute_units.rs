@@ -0,0 +1,121 @@
+//! Compute-unit ceilings for the trading hot path, and the saving from
+//! storing the curve zero-copy. Compute units are only metered for the SBF
+//! build, so these only build with the `test-sbf` feature and run with:
+//!
+//!     cargo test-sbf --features mock-oracle,test-sbf --test compute_units -- --nocapture
+
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use common::*;
+use pump_clone::state::BondingCurve;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// A buy reads and writes the curve in place. Going back to Borsh-decoding
+/// the whole curve on every trade pushes a buy over this ceiling.
+const BUY_CU_CEILING: u64 = 60_000;
+
+/// The quote path only loads the curve and runs the curve math.
+const QUOTE_BUY_CU_CEILING: u64 = 10_000;
+
+/// Baseline entry holding the CU of the same buy on the program as it was
+/// before the curve moved to zero-copy (8bce661), when every trade
+/// Borsh-decoded and re-encoded the whole curve. It is recorded once by hand
+/// from an SBF build of that commit and kept by `UPDATE_CU_BASELINE`, which
+/// only rewrites the entries it measures.
+const BORSH_BUY_BASELINE: &str = "buy_tokens_borsh";
+
+#[tokio::test]
+async fn buy_stays_within_compute_ceiling() {
+    assert!(running_sbf(), "compute units are only metered for the SBF build; run under cargo test-sbf");
+
+    let units = measure_buy().await;
+
+    println!("buy: {units} CU");
+    assert!(units <= BUY_CU_CEILING, "buy used {units} CU, ceiling is {BUY_CU_CEILING}");
+}
+
+#[tokio::test]
+async fn zero_copy_buy_uses_fewer_compute_units_than_borsh() {
+    assert!(running_sbf(), "compute units are only metered for the SBF build; run under cargo test-sbf");
+
+    let borsh_units = *baseline().get(BORSH_BUY_BASELINE).unwrap_or_else(|| {
+        panic!("tests/compute_units.baseline has no `{BORSH_BUY_BASELINE}` entry")
+    });
+    let units = measure_buy().await;
+
+    let saved = borsh_units.saturating_sub(units);
+    println!(
+        "buy: {units} CU zero-copy, {borsh_units} CU Borsh, {saved} CU ({:.1}%) saved",
+        saved as f64 * 100.0 / borsh_units as f64
+    );
+    assert!(
+        units < borsh_units,
+        "zero-copy buy used {units} CU, no less than the {borsh_units} CU Borsh buy"
+    );
+}
+
+#[tokio::test]
+async fn quote_buy_stays_within_compute_ceiling() {
+    assert!(running_sbf(), "compute units are only metered for the SBF build; run under cargo test-sbf");
+
+    let launch = Launch::new();
+    let payer = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(payer.pubkey(), system_account(1_000_000_000));
+    let mut context = program_test.start_with_context().await;
+
+    let units = execute(&mut context, &[quote_buy_ix(&launch, 1_000_000_000)], &payer, &[])
+        .await
+        .unwrap();
+
+    println!("quote_buy: {units} CU");
+    assert!(
+        units <= QUOTE_BUY_CU_CEILING,
+        "quote_buy used {units} CU, ceiling is {QUOTE_BUY_CU_CEILING}"
+    );
+}
+
+/// The same first buy on a fresh curve the `buy_tokens` benchmark measures.
+async fn measure_buy() -> u64 {
+    let launch = Launch::new();
+    let buyer = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(buyer.pubkey(), system_account(10_000_000_000));
+    let mut context = program_test.start_with_context().await;
+
+    let units = execute(
+        &mut context,
+        &[buy_ix(&launch, &buyer.pubkey(), 1_000_000_000, 0)],
+        &buyer,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.trade_count, 1);
+    assert!(curve.real_sol_reserves > 0);
+
+    units
+}
+
+/// `name units` pairs from `tests/compute_units.baseline`, written by the
+/// `benchmarks` test.
+fn baseline() -> BTreeMap<String, u64> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compute_units.baseline");
+
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let (name, units) = line.split_once(char::is_whitespace)?;
+            Some((name.to_string(), units.trim().parse().ok()?))
+        })
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! Config changes that go through the timelock, and holder governance
+//! exercising the platform authority it has taken over.
+
+mod common;
+
+use common::*;
+use pump_clone::{
+    error::PumpCloneError,
+    governance::state::{Governance, GovernanceProposal},
+    state::{AdminAction, ConfigUpdate, GlobalState},
+};
+use solana_sdk::{
+    program_option::COption,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const USER_LAMPORTS: u64 = 100_000_000_000;
+const CONFIG_TIMELOCK: i64 = 60 * 60;
+const QUORUM_VOTES: u64 = 100;
+const PROPOSAL_THRESHOLD: u64 = 10;
+const VOTING_PERIOD: i64 = Governance::MIN_VOTING_PERIOD;
+/// Comfortably after the genesis clock, so the tests can move time forward
+/// from a known point.
+const LATER: i64 = 2_000_000_000;
+
+fn fee_update() -> ConfigUpdate {
+    ConfigUpdate {
+        platform_fee_bps: Some(PLATFORM_FEE_BPS * 2),
+        ..ConfigUpdate::default()
+    }
+}
+
+#[tokio::test]
+async fn queued_config_changes_wait_out_the_timelock() {
+    let launch = Launch::new();
+    let mut program_test = bare_program_test(&launch);
+    add_platform_with(&mut program_test, &launch, |global_state| {
+        global_state.config_timelock = CONFIG_TIMELOCK;
+    });
+    let mut context = program_test.start_with_context().await;
+    set_unix_timestamp(&mut context, LATER).await;
+
+    execute(
+        &mut context,
+        &[queue_config_change_ix(&launch, 0, fee_update())],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // One second short of the timelock
+    set_unix_timestamp(&mut context, LATER + CONFIG_TIMELOCK - 1).await;
+    assert_program_error(
+        execute(&mut context, &[execute_config_change_ix(&launch, 0)], &launch.authority, &[])
+            .await,
+        PumpCloneError::TimelockPeriodNotElapsed.into(),
+    );
+    let global_state: GlobalState = load_anchor(&mut context, launch.global_state).await;
+    assert_eq!(global_state.platform_fee_bps, PLATFORM_FEE_BPS);
+
+    set_unix_timestamp(&mut context, LATER + CONFIG_TIMELOCK).await;
+    execute(&mut context, &[execute_config_change_ix(&launch, 0)], &launch.authority, &[])
+        .await
+        .unwrap();
+
+    let global_state: GlobalState = load_anchor(&mut context, launch.global_state).await;
+    assert_eq!(global_state.platform_fee_bps, PLATFORM_FEE_BPS * 2);
+    let pending = context.banks_client.get_account(config_change(0)).await.unwrap();
+    assert!(pending.is_none(), "an executed change is closed");
+}
+
+#[tokio::test]
+async fn holders_vote_config_changes_through() {
+    let launch = Launch::new();
+    let governance_mint = Pubkey::new_unique();
+    let voter = Keypair::new();
+    let minnow = Keypair::new();
+
+    let mut program_test = bare_program_test(&launch);
+    add_platform_with(&mut program_test, &launch, |global_state| {
+        global_state.authority = governance();
+    });
+    add_anchor_account(
+        &mut program_test,
+        governance(),
+        &Governance {
+            governance_mint,
+            quorum_votes: QUORUM_VOTES,
+            proposal_threshold: PROPOSAL_THRESHOLD,
+            voting_period: VOTING_PERIOD,
+            execution_delay: 0,
+            proposal_count: 0,
+            bump: Pubkey::find_program_address(&[b"governance"], &pump_clone::ID).1,
+        },
+        Governance::LEN,
+    );
+    program_test.add_account(
+        governance_mint,
+        packed_account(
+            spl_token::state::Mint {
+                mint_authority: COption::None,
+                supply: QUORUM_VOTES + PROPOSAL_THRESHOLD,
+                decimals: 0,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            spl_token::ID,
+        ),
+    );
+    program_test.add_account(
+        get_associated_token_address(&governance(), &governance_mint),
+        token_account(governance_mint, governance(), 0),
+    );
+    for (holder, votes) in [(&voter, QUORUM_VOTES), (&minnow, PROPOSAL_THRESHOLD - 1)] {
+        program_test.add_account(holder.pubkey(), system_account(USER_LAMPORTS));
+        program_test.add_account(
+            get_associated_token_address(&holder.pubkey(), &governance_mint),
+            token_account(governance_mint, holder.pubkey(), votes),
+        );
+    }
+    let mut context = program_test.start_with_context().await;
+
+    set_unix_timestamp(&mut context, LATER).await;
+    for (holder, votes) in [(&voter, QUORUM_VOTES), (&minnow, PROPOSAL_THRESHOLD - 1)] {
+        execute(
+            &mut context,
+            &[deposit_votes_ix(&governance_mint, &holder.pubkey(), votes)],
+            holder,
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    // Proposing takes at least the proposal threshold in deposited votes
+    set_unix_timestamp(&mut context, LATER + 1).await;
+    let update = AdminAction::UpdateConfig(fee_update());
+    assert_program_error(
+        execute(
+            &mut context,
+            &[create_proposal_ix(&minnow.pubkey(), 0, update.clone())],
+            &minnow,
+            &[],
+        )
+        .await,
+        PumpCloneError::ProposalThresholdNotMet.into(),
+    );
+
+    execute(&mut context, &[create_proposal_ix(&voter.pubkey(), 0, update)], &voter, &[])
+        .await
+        .unwrap();
+    execute(&mut context, &[cast_vote_ix(&voter.pubkey(), 0, true)], &voter, &[])
+        .await
+        .unwrap();
+
+    // Votes count only once the voting period is over
+    assert_program_error(
+        execute(
+            &mut context,
+            &[execute_proposal_ix(&launch, &voter.pubkey(), 0)],
+            &voter,
+            &[],
+        )
+        .await,
+        PumpCloneError::TimelockPeriodNotElapsed.into(),
+    );
+
+    set_unix_timestamp(&mut context, LATER + 1 + VOTING_PERIOD).await;
+    execute(&mut context, &[execute_proposal_ix(&launch, &voter.pubkey(), 0)], &voter, &[])
+        .await
+        .unwrap();
+
+    let global_state: GlobalState = load_anchor(&mut context, launch.global_state).await;
+    assert_eq!(global_state.platform_fee_bps, PLATFORM_FEE_BPS * 2);
+    let proposal: GovernanceProposal = load_anchor(&mut context, governance_proposal(0)).await;
+    assert!(proposal.executed);
+    assert_eq!(proposal.votes_for, QUORUM_VOTES);
+}
\ No newline at end of file
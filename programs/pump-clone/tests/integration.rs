@@ -0,0 +1,995 @@
+//! End-to-end tests of the platform lifecycle: initialize, create, buy,
+//! sell, complete and withdraw, plus the paths that must be rejected along
+//! the way.
+//!
+//! Runs fully offline with `cargo test --test integration`, natively and
+//! against Metaplex loaded from `tests/fixtures/` (or the stand-in in
+//! `common` until that fixture is committed; see the README there).
+//! `cargo test-sbf --features mock-oracle` runs the same tests against the
+//! SBF build of the program.
+
+mod common;
+
+use anchor_lang::Discriminator;
+use common::*;
+use pump_clone::{
+    curve::{self, CurveReserves},
+    error::PumpCloneError,
+    oracle::USD_SCALE,
+    state::{
+        AdminAction, BondingCurve, BondingCurveV0, GlobalState, TokenMetadata, VersionedAccount,
+    },
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const TRADER_LAMPORTS: u64 = 100_000_000_000;
+const SMALL_BUY: u64 = 1_000_000_000;
+/// A SOL price at which even a fresh curve is past the graduation market
+/// cap.
+const GRADUATION_SOL_USD_PRICE: u64 = 10_000 * USD_SCALE;
+
+#[tokio::test]
+async fn full_lifecycle() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let mut program_test = bare_program_test(&launch);
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    // Initialize
+    execute(&mut context, &[initialize_platform_ix(&launch)], &launch.authority, &[])
+        .await
+        .unwrap();
+
+    let global_state: GlobalState = load_anchor(&mut context, launch.global_state).await;
+    assert_eq!(global_state.authority, launch.authority.pubkey());
+    assert_eq!(global_state.platform_fee_bps, PLATFORM_FEE_BPS);
+    assert_eq!(global_state.oracle_feed, launch.oracle_feed);
+    assert_eq!(global_state.pause_flags, 0);
+    let fees_before = lamports(&mut context, launch.fee_vault).await;
+
+    // Create
+    let mint = Keypair::new();
+    let params = create_token_params();
+    execute(
+        &mut context,
+        &[create_token_ix(&launch, &mint.pubkey(), params.clone())],
+        &launch.creator,
+        &[&mint],
+    )
+    .await
+    .unwrap();
+    let launch = launch.with_mint(mint.pubkey());
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.mint, launch.mint);
+    assert_eq!(curve.creator, launch.creator.pubkey());
+    assert_eq!(curve.virtual_sol_reserves, INITIAL_VIRTUAL_SOL_RESERVES);
+    assert_eq!(curve.virtual_token_reserves, INITIAL_VIRTUAL_TOKEN_RESERVES);
+    assert_eq!(curve.real_token_reserves, INITIAL_REAL_TOKEN_RESERVES);
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert!(!curve.is_complete());
+    assert_eq!(
+        token_balance(&mut context, launch.bonding_curve_token_account).await,
+        INITIAL_REAL_TOKEN_RESERVES
+    );
+
+    let token_metadata: TokenMetadata = load_anchor(&mut context, launch.token_metadata).await;
+    assert_eq!(token_metadata.name, params.name);
+    assert_eq!(token_metadata.symbol, params.symbol);
+
+    // What create_token asked Metaplex to write
+    let metadata = context
+        .banks_client
+        .get_account(metaplex_metadata(&launch.mint))
+        .await
+        .unwrap()
+        .expect("metadata account exists");
+    assert_eq!(metadata.owner, mpl_token_metadata::ID);
+    let (name, symbol, uri) = metadata_name_symbol_uri(&metadata.data);
+    assert_eq!(name, params.name);
+    assert_eq!(symbol, params.symbol);
+    assert_eq!(uri, params.uri);
+
+    // Buy, with the exact expected output as the slippage floor
+    let buy = curve::quote_buy(&CurveReserves::from(&curve), SMALL_BUY, PLATFORM_FEE_BPS).unwrap();
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, buy.token_amount)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let trader_tokens = launch.token_account(&trader.pubkey());
+    assert_eq!(token_balance(&mut context, trader_tokens).await, buy.token_amount);
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, SMALL_BUY - buy.fee_amount);
+    assert_eq!(
+        curve.real_token_reserves,
+        INITIAL_REAL_TOKEN_RESERVES - buy.token_amount
+    );
+    assert_eq!(curve.trade_count, 1);
+
+    // Sell half back
+    let tokens_sold = buy.token_amount / 2;
+    let sell =
+        curve::quote_sell(&CurveReserves::from(&curve), tokens_sold, PLATFORM_FEE_BPS).unwrap();
+    let trader_before = lamports(&mut context, trader.pubkey()).await;
+    execute(
+        &mut context,
+        &[sell_ix(&launch, &trader.pubkey(), tokens_sold, sell.net_sol_amount)],
+        &launch.creator,
+        &[&trader],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, trader_tokens).await,
+        buy.token_amount - tokens_sold
+    );
+    assert_eq!(
+        lamports(&mut context, trader.pubkey()).await,
+        trader_before + sell.net_sol_amount
+    );
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, SMALL_BUY - buy.fee_amount - sell.sol_amount);
+    assert_eq!(
+        lamports(&mut context, launch.fee_vault).await,
+        fees_before + buy.fee_amount + sell.fee_amount
+    );
+
+    // Completing below the graduation market cap is rejected
+    let authority = launch.authority.pubkey();
+    assert_program_error(
+        execute(
+            &mut context,
+            &[complete_bonding_curve_ix(&launch, &authority)],
+            &launch.authority,
+            &[],
+        )
+        .await,
+        PumpCloneError::GraduationMarketCapNotReached.into(),
+    );
+
+    // The SOL price moves the curve past graduation without a trade
+    set_sol_usd_price(&mut context, &launch, GRADUATION_SOL_USD_PRICE).await;
+    execute(
+        &mut context,
+        &[complete_bonding_curve_ix(&launch, &authority)],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert!(curve.is_complete());
+
+    // A completed curve no longer trades
+    assert_program_error(
+        execute(
+            &mut context,
+            &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::TokenAlreadyGraduated.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[sell_ix(&launch, &trader.pubkey(), 1, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::TokenAlreadyGraduated.into(),
+    );
+
+    // Withdraw the liquidity for migration
+    let curve_tokens = token_balance(&mut context, launch.bonding_curve_token_account).await;
+    let fees_before_withdraw = lamports(&mut context, launch.fee_vault).await;
+    execute(&mut context, &[withdraw_ix(&launch, &authority)], &launch.authority, &[])
+        .await
+        .unwrap();
+
+    let migration_fee = MIGRATION_FEE.min(curve.real_sol_reserves);
+    assert_eq!(
+        token_balance(&mut context, launch.token_account(&authority)).await,
+        curve_tokens
+    );
+    assert_eq!(
+        token_balance(&mut context, launch.bonding_curve_token_account).await,
+        0
+    );
+    assert_eq!(
+        lamports(&mut context, launch.fee_vault).await,
+        fees_before_withdraw + migration_fee
+    );
+    assert_eq!(
+        lamports(&mut context, launch.bonding_curve).await,
+        rent_exempt(BondingCurve::LEN)
+    );
+    let migrated: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(migrated.real_sol_reserves, 0);
+    assert_eq!(migrated.real_token_reserves, 0);
+
+    // Hand the authority to a one-of-one admin multisig and withdraw
+    // everything the platform collected through it
+    let fees = buy.fee_amount + sell.fee_amount + migration_fee;
+    assert_eq!(lamports(&mut context, launch.fee_vault).await, fees_before + fees);
+
+    let multisig = admin_multisig();
+    execute(
+        &mut context,
+        &[
+            create_admin_multisig_ix(&launch, vec![authority], 1),
+            propose_authority_ix(&launch, &authority, multisig),
+            create_admin_proposal_ix(&authority, 0, AdminAction::AcceptAuthority),
+            execute_admin_proposal_ix(&launch, &authority, 0, authority),
+        ],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+    let global_state: GlobalState = load_anchor(&mut context, launch.global_state).await;
+    assert_eq!(global_state.authority, multisig);
+
+    let recipient = Keypair::new().pubkey();
+    execute(
+        &mut context,
+        &[
+            create_admin_proposal_ix(
+                &authority,
+                1,
+                AdminAction::WithdrawFees {
+                    amount: fees,
+                    recipient,
+                },
+            ),
+            execute_admin_proposal_ix(&launch, &authority, 1, recipient),
+        ],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(lamports(&mut context, launch.fee_vault).await, fees_before);
+    assert_eq!(lamports(&mut context, recipient).await, fees);
+}
+
+#[tokio::test]
+async fn paused_platform_rejects_launches_and_trades() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    // Hold tokens to sell before the pause
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+    set_paused(&mut context, &launch, true).await;
+
+    let mint = Keypair::new();
+    assert_program_error(
+        execute(
+            &mut context,
+            &[create_token_ix(&launch, &mint.pubkey(), create_token_params())],
+            &launch.creator,
+            &[&mint],
+        )
+        .await,
+        PumpCloneError::EmergencyPauseActivated.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::EmergencyPauseActivated.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[sell_ix(&launch, &trader.pubkey(), 1, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::EmergencyPauseActivated.into(),
+    );
+
+    set_paused(&mut context, &launch, false).await;
+    execute(
+        &mut context,
+        &[sell_ix(&launch, &trader.pubkey(), 1, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn slippage_limits_are_enforced() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    let buy = curve::quote_buy(&CurveReserves::from(&curve), SMALL_BUY, PLATFORM_FEE_BPS).unwrap();
+
+    assert_program_error(
+        execute(
+            &mut context,
+            &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, buy.token_amount + 1)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::SlippageToleranceExceeded.into(),
+    );
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, buy.token_amount)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    let sell = curve::quote_sell(
+        &CurveReserves::from(&curve),
+        buy.token_amount,
+        PLATFORM_FEE_BPS,
+    )
+    .unwrap();
+
+    assert_program_error(
+        execute(
+            &mut context,
+            &[sell_ix(&launch, &trader.pubkey(), buy.token_amount, sell.net_sol_amount + 1)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::SlippageToleranceExceeded.into(),
+    );
+    execute(
+        &mut context,
+        &[sell_ix(&launch, &trader.pubkey(), buy.token_amount, sell.net_sol_amount)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn buy_beyond_real_token_reserves_is_clipped() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let remaining = 1_000_000;
+    let mut program_test = program_test_with_curve(&launch, |curve| {
+        curve.real_token_reserves = remaining;
+    });
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    let clipped =
+        curve::quote_buy_exact_out(&CurveReserves::from(&curve), remaining, PLATFORM_FEE_BPS)
+            .unwrap();
+    assert!(clipped.sol_amount < SMALL_BUY);
+    assert!(clipped.completes_curve);
+
+    let trader_before = lamports(&mut context, trader.pubkey()).await;
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // Only the remaining reserves are sold, and only their cost is charged.
+    // The trader also paid the transaction fee and the rent for its new
+    // accounts, so the charge is checked through the curve and fee vault.
+    assert_eq!(
+        token_balance(&mut context, launch.token_account(&trader.pubkey())).await,
+        remaining
+    );
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.real_token_reserves, 0);
+    assert_eq!(curve.real_sol_reserves, clipped.sol_amount - clipped.fee_amount);
+    // Selling out completes the curve below the graduation market cap
+    assert!(curve.is_complete());
+    assert_eq!(
+        lamports(&mut context, launch.fee_vault).await,
+        rent_exempt(0) + clipped.fee_amount
+    );
+    assert!(trader_before - lamports(&mut context, trader.pubkey()).await < SMALL_BUY);
+}
+
+#[tokio::test]
+async fn sell_beyond_real_sol_reserves_is_rejected() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let tokens_held = 10_000_000_000_000;
+    // Tokens minted outside the curve: pricing them needs more SOL than the
+    // curve has raised
+    let mut program_test = program_test_with_curve(&launch, |curve| {
+        curve.real_sol_reserves = 1_000;
+    });
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    program_test.add_account(
+        launch.token_account(&trader.pubkey()),
+        token_account(launch.mint, trader.pubkey(), tokens_held),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    assert_program_error(
+        execute(
+            &mut context,
+            &[sell_ix(&launch, &trader.pubkey(), tokens_held, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::InsufficientFunds.into(),
+    );
+    assert_eq!(
+        token_balance(&mut context, launch.token_account(&trader.pubkey())).await,
+        tokens_held
+    );
+}
+
+#[tokio::test]
+async fn trades_go_through_without_an_oracle_price() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    // The feed reports no price, so trades go through without touching
+    // graduation
+    set_sol_usd_price(&mut context, &launch, 0).await;
+    execute(
+        &mut context,
+        &[
+            buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0),
+            sell_ix(&launch, &trader.pubkey(), 1_000, 0),
+        ],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert!(!curve.is_complete());
+    assert_eq!(curve.trade_count, 2);
+    let token_metadata: TokenMetadata = load_anchor(&mut context, launch.token_metadata).await;
+    assert_eq!(token_metadata.market_cap, curve.market_cap().unwrap());
+
+    let authority = launch.authority.pubkey();
+    assert_program_error(
+        execute(
+            &mut context,
+            &[complete_bonding_curve_ix(&launch, &authority)],
+            &launch.authority,
+            &[],
+        )
+        .await,
+        PumpCloneError::OraclePriceFeedUnavailable.into(),
+    );
+
+    // Graduation resumes with the next trade once the feed has a price
+    set_sol_usd_price(&mut context, &launch, GRADUATION_SOL_USD_PRICE).await;
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert!(curve.is_complete());
+}
+
+#[tokio::test]
+async fn blocked_wallets_cannot_trade() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+    execute(
+        &mut context,
+        &[block_wallet_ix(&launch, trader.pubkey(), "sanctioned")],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_program_error(
+        execute(
+            &mut context,
+            &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::WalletBlacklisted.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[sell_ix(&launch, &trader.pubkey(), 1, 0)],
+            &trader,
+            &[],
+        )
+        .await,
+        PumpCloneError::WalletBlacklisted.into(),
+    );
+
+    // The blocklist PDA can't be swapped for some other empty account
+    let mut dodge = sell_ix(&launch, &trader.pubkey(), 1, 0);
+    let blocked_wallet = launch.blocked_wallet(&trader.pubkey());
+    for meta in &mut dodge.accounts {
+        if meta.pubkey == blocked_wallet {
+            meta.pubkey = launch.blocked_wallet(&launch.creator.pubkey());
+        }
+    }
+    assert_program_error(
+        execute(&mut context, &[dodge], &trader, &[]).await,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    execute(
+        &mut context,
+        &[unblock_wallet_ix(&launch, trader.pubkey())],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+    execute(
+        &mut context,
+        &[sell_ix(&launch, &trader.pubkey(), 1, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn admin_instructions_require_the_platform_authority() {
+    let launch = Launch::new();
+    let intruder = Keypair::new();
+    let mut program_test = program_test_with_curve(&launch, |curve| {
+        curve.real_sol_reserves = SMALL_BUY;
+        curve.complete = 1;
+    });
+    program_test.add_account(intruder.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+    let intruder_key = intruder.pubkey();
+
+    assert_program_error(
+        execute(
+            &mut context,
+            &[pause_ix(&launch, &intruder_key, GlobalState::PAUSE_ALL)],
+            &intruder,
+            &[],
+        )
+        .await,
+        PumpCloneError::UnauthorizedAdmin.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[propose_authority_ix(&launch, &intruder_key, intruder_key)],
+            &intruder,
+            &[],
+        )
+        .await,
+        PumpCloneError::UnauthorizedAdmin.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[withdraw_ix(&launch, &intruder_key)],
+            &intruder,
+            &[],
+        )
+        .await,
+        PumpCloneError::UnauthorizedAdmin.into(),
+    );
+
+    let global_state: GlobalState = load_anchor(&mut context, launch.global_state).await;
+    assert_eq!(global_state.authority, launch.authority.pubkey());
+    assert_eq!(global_state.pending_authority, None);
+    assert_eq!(global_state.pause_flags, 0);
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, SMALL_BUY);
+
+    // Nor can anyone but the authority lift a pause
+    assert_program_error(
+        execute(
+            &mut context,
+            &[unpause_ix(&launch, &intruder_key, GlobalState::PAUSE_ALL)],
+            &intruder,
+            &[],
+        )
+        .await,
+        PumpCloneError::UnauthorizedAdmin.into(),
+    );
+}
+
+#[tokio::test]
+async fn curves_owing_refunds_cannot_complete_or_withdraw() {
+    let launch = Launch::new();
+    let authority = launch.authority.pubkey();
+
+    // Past its deadline the curve only pays out refunds, however high the
+    // SOL price goes
+    let mut context = program_test_with_curve(&launch, |curve| {
+        curve.real_sol_reserves = SMALL_BUY;
+        curve.deadline = 1;
+    })
+    .start_with_context()
+    .await;
+    set_sol_usd_price(&mut context, &launch, GRADUATION_SOL_USD_PRICE).await;
+    assert_program_error(
+        execute(
+            &mut context,
+            &[complete_bonding_curve_ix(&launch, &authority)],
+            &launch.authority,
+            &[],
+        )
+        .await,
+        PumpCloneError::CurveDeadlinePassed.into(),
+    );
+
+    // Nor can the liquidity of a curve that has paid refunds be withdrawn
+    let mut context = program_test_with_curve(&launch, |curve| {
+        curve.real_sol_reserves = SMALL_BUY;
+        curve.complete = 1;
+        curve.refund_token_supply = 1_000;
+    })
+    .start_with_context()
+    .await;
+    assert_program_error(
+        execute(&mut context, &[withdraw_ix(&launch, &authority)], &launch.authority, &[]).await,
+        PumpCloneError::CurveDeadlinePassed.into(),
+    );
+
+    // A curve that graduated before its deadline owes no refunds, so it can
+    // still be withdrawn once the deadline has passed
+    let mut context = program_test_with_curve(&launch, |curve| {
+        curve.real_sol_reserves = SMALL_BUY;
+        curve.complete = 1;
+        curve.deadline = 1;
+    })
+    .start_with_context()
+    .await;
+    execute(&mut context, &[withdraw_ix(&launch, &authority)], &launch.authority, &[])
+        .await
+        .unwrap();
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, 0);
+}
+
+#[tokio::test]
+async fn multisig_completes_and_withdraws_a_curve() {
+    let launch = Launch::new();
+    let trader = Keypair::new();
+    let cosigner = Keypair::new();
+    let mut program_test = program_test(&launch);
+    program_test.add_account(trader.pubkey(), system_account(TRADER_LAMPORTS));
+    program_test.add_account(cosigner.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+    let authority = launch.authority.pubkey();
+    let recipient = Keypair::new().pubkey();
+
+    // Hand the authority and the fee recipient to a two-of-two multisig
+    execute(
+        &mut context,
+        &[
+            create_admin_multisig_ix(&launch, vec![authority, cosigner.pubkey()], 2),
+            propose_authority_ix(&launch, &authority, admin_multisig()),
+            create_admin_proposal_ix(&authority, 0, AdminAction::AcceptAuthority),
+            approve_admin_proposal_ix(&cosigner.pubkey(), 0),
+            execute_admin_proposal_ix(&launch, &authority, 0, authority),
+        ],
+        &launch.authority,
+        &[&cosigner],
+    )
+    .await
+    .unwrap();
+    execute(
+        &mut context,
+        &[
+            create_admin_proposal_ix(
+                &authority,
+                1,
+                AdminAction::SetFeeRecipient {
+                    fee_recipient: recipient,
+                },
+            ),
+            approve_admin_proposal_ix(&cosigner.pubkey(), 1),
+            execute_admin_proposal_ix(&launch, &authority, 1, authority),
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &authority,
+                &recipient,
+                &launch.mint,
+                &spl_token::ID,
+            ),
+        ],
+        &launch.authority,
+        &[&cosigner],
+    )
+    .await
+    .unwrap();
+
+    execute(
+        &mut context,
+        &[buy_ix(&launch, &trader.pubkey(), SMALL_BUY, 0)],
+        &trader,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // A curve that hasn't completed can't be withdrawn
+    let mint = launch.mint;
+    execute(
+        &mut context,
+        &[
+            create_admin_proposal_ix(&authority, 2, AdminAction::Withdraw { mint }),
+            approve_admin_proposal_ix(&cosigner.pubkey(), 2),
+            create_admin_proposal_ix(&authority, 3, AdminAction::CompleteBondingCurve { mint }),
+        ],
+        &launch.authority,
+        &[&cosigner],
+    )
+    .await
+    .unwrap();
+    assert_program_error(
+        execute(
+            &mut context,
+            &[execute_curve_admin_proposal_ix(&launch, &authority, 2, recipient)],
+            &launch.authority,
+            &[],
+        )
+        .await,
+        PumpCloneError::CurveNotComplete.into(),
+    );
+
+    // Completing needs both signers, and the graduation market cap
+    assert_program_error(
+        execute(
+            &mut context,
+            &[execute_curve_admin_proposal_ix(&launch, &authority, 3, recipient)],
+            &launch.authority,
+            &[],
+        )
+        .await,
+        PumpCloneError::MultiSigThresholdNotMet.into(),
+    );
+    execute(
+        &mut context,
+        &[approve_admin_proposal_ix(&cosigner.pubkey(), 3)],
+        &cosigner,
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_program_error(
+        execute(
+            &mut context,
+            &[execute_curve_admin_proposal_ix(&launch, &authority, 3, recipient)],
+            &launch.authority,
+            &[],
+        )
+        .await,
+        PumpCloneError::GraduationMarketCapNotReached.into(),
+    );
+
+    set_sol_usd_price(&mut context, &launch, GRADUATION_SOL_USD_PRICE).await;
+    execute(
+        &mut context,
+        &[execute_curve_admin_proposal_ix(&launch, &authority, 3, recipient)],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert!(curve.is_complete());
+
+    // The liquidity goes to the fee recipient, less the migration fee
+    let curve_tokens = token_balance(&mut context, launch.bonding_curve_token_account).await;
+    let fees_before = lamports(&mut context, launch.fee_vault).await;
+    execute(
+        &mut context,
+        &[execute_curve_admin_proposal_ix(&launch, &authority, 2, recipient)],
+        &launch.authority,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let migration_fee = MIGRATION_FEE.min(curve.real_sol_reserves);
+    assert_eq!(
+        lamports(&mut context, recipient).await,
+        curve.real_sol_reserves - migration_fee
+    );
+    assert_eq!(
+        token_balance(&mut context, launch.token_account(&recipient)).await,
+        curve_tokens
+    );
+    assert_eq!(
+        lamports(&mut context, launch.fee_vault).await,
+        fees_before + migration_fee
+    );
+    let migrated: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(migrated.real_sol_reserves, 0);
+    assert_eq!(migrated.real_token_reserves, 0);
+}
+
+#[tokio::test]
+async fn version_0_curves_upgrade_to_the_zero_copy_layout() {
+    let launch = Launch::new();
+    let authority = launch.authority.pubkey();
+    let intruder = Keypair::new();
+    let bump = curve_bump(&launch);
+
+    let mut program_test = bare_program_test(&launch);
+    add_platform(&mut program_test, &launch);
+    program_test.add_account(launch.bonding_curve, program_account(version_0_curve(&launch, bump)));
+    program_test.add_account(intruder.pubkey(), system_account(TRADER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    // Only the platform authority may migrate accounts
+    assert_program_error(
+        execute(
+            &mut context,
+            &[upgrade_account_ix(
+                &launch,
+                &intruder.pubkey(),
+                launch.bonding_curve,
+                VersionedAccount::BondingCurve,
+            )],
+            &intruder,
+            &[],
+        )
+        .await,
+        PumpCloneError::UnauthorizedAdmin.into(),
+    );
+
+    let upgrade = upgrade_account_ix(
+        &launch,
+        &authority,
+        launch.bonding_curve,
+        VersionedAccount::BondingCurve,
+    );
+    execute(&mut context, std::slice::from_ref(&upgrade), &launch.authority, &[])
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(launch.bonding_curve)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), BondingCurve::LEN);
+    assert_eq!(account.lamports, rent_exempt(BondingCurve::LEN));
+
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.version, BondingCurve::VERSION);
+    assert_eq!(curve.mint, launch.mint);
+    assert_eq!(curve.creator, launch.creator.pubkey());
+    assert_eq!(curve.virtual_token_reserves, INITIAL_VIRTUAL_TOKEN_RESERVES);
+    assert_eq!(curve.virtual_sol_reserves, INITIAL_VIRTUAL_SOL_RESERVES + SMALL_BUY);
+    assert_eq!(curve.real_sol_reserves, SMALL_BUY);
+    assert_eq!(curve.complete, 0);
+    assert_eq!(curve.allowlist_root, [0; 32]);
+    assert_eq!(curve.presale_merkle_root, [9; 32]);
+    assert_eq!(u128::from_le_bytes(curve.price_cumulative), 1 << 80);
+    assert_eq!(curve.trade_count, 4);
+    assert_eq!(curve.deadline, 1_000_000);
+    assert_eq!(curve.bump, bump);
+
+    // The upgraded curve is at the current version already
+    assert_program_error(
+        execute(&mut context, &[upgrade], &launch.authority, &[]).await,
+        PumpCloneError::AccountAlreadyUpgraded.into(),
+    );
+}
+
+/// A curve as a pre-versioning build wrote it, encoded field by field
+/// rather than through `BondingCurveV0` so the fixture pins the old layout.
+fn version_0_curve(launch: &Launch, bump: u8) -> Vec<u8> {
+    let mut data = BondingCurve::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(launch.mint.as_ref());
+    data.extend_from_slice(launch.creator.pubkey().as_ref());
+    data.extend_from_slice(&INITIAL_VIRTUAL_TOKEN_RESERVES.to_le_bytes());
+    data.extend_from_slice(&(INITIAL_VIRTUAL_SOL_RESERVES + SMALL_BUY).to_le_bytes());
+    data.extend_from_slice(&INITIAL_REAL_TOKEN_RESERVES.to_le_bytes());
+    data.extend_from_slice(&SMALL_BUY.to_le_bytes());
+    data.extend_from_slice(&TOTAL_SUPPLY.to_le_bytes());
+    data.push(0); // complete
+    data.extend_from_slice(&0i64.to_le_bytes()); // created_at
+    data.push(0); // allowlist_root: None
+    data.extend_from_slice(&[9; 32]); // presale_merkle_root
+    data.extend_from_slice(&[0; 8 * 4]); // presale allocation, sold, raised, ends_at
+    data.push(0); // presale_finalized
+    data.extend_from_slice(&(1u128 << 80).to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes()); // last_price_update
+    data.extend_from_slice(&4u64.to_le_bytes()); // trade_count
+    data.push(1); // deadline: Some
+    data.extend_from_slice(&1_000_000i64.to_le_bytes());
+    data.extend_from_slice(&INITIAL_REAL_TOKEN_RESERVES.to_le_bytes());
+    data.extend_from_slice(&[0; 8 * 3]); // refund pool, supply, refunded
+    data.push(bump);
+
+    // The account was allocated for a `Some` allowlist root
+    data.resize(BondingCurveV0::LEN, 0);
+    data
+}
+
+fn curve_bump(launch: &Launch) -> u8 {
+    Pubkey::find_program_address(&[b"bonding_curve", launch.mint.as_ref()], &pump_clone::ID).1
+}
+
+async fn set_paused(context: &mut ProgramTestContext, launch: &Launch, paused: bool) {
+    let authority = launch.authority.pubkey();
+    let instruction = if paused {
+        pause_ix(launch, &authority, GlobalState::PAUSE_ALL)
+    } else {
+        unpause_ix(launch, &authority, GlobalState::PAUSE_ALL)
+    };
+
+    execute(context, &[instruction], &launch.authority, &[])
+        .await
+        .unwrap();
+}
\ No newline at end of file
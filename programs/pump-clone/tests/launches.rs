@@ -0,0 +1,282 @@
+//! Launch options outside the public curve: allowlisted presales, vested
+//! creator allocations, refunds after a missed deadline, and closing a dead
+//! launch.
+
+mod common;
+
+use common::*;
+use pump_clone::{
+    error::PumpCloneError,
+    state::{BondingCurve, VestingEscrow},
+    utils::presale_leaf,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const USER_LAMPORTS: u64 = 100_000_000_000;
+const PRESALE_ALLOCATION: u64 = 10_000_000_000_000;
+const TOKENS_SOLD: u64 = 100_000_000_000_000;
+const SOL_RAISED: u64 = 3_000_000_000;
+/// Comfortably after the genesis clock, so the tests can move time both ways
+/// around it.
+const LATER: i64 = 2_000_000_000;
+
+#[tokio::test]
+async fn presale_sells_only_to_the_allowlist_before_public_trading() {
+    let launch = Launch::new();
+    let buyer = Keypair::new();
+    let buyer_key = buyer.pubkey();
+    let mut program_test = program_test_with_curve(&launch, |curve| {
+        curve.presale_merkle_root = presale_leaf(&buyer_key, PRESALE_ALLOCATION);
+        curve.presale_allocation = PRESALE_ALLOCATION;
+        curve.presale_ends_at = i64::MAX;
+    });
+    program_test.add_account(buyer_key, system_account(USER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    // The public curve stays shut while the presale runs
+    assert_program_error(
+        execute(&mut context, &[buy_ix(&launch, &buyer_key, SOL_RAISED, 0)], &buyer, &[]).await,
+        PumpCloneError::PresaleActive.into(),
+    );
+
+    // The proof only holds for the allocation the wallet was allowlisted for
+    assert_program_error(
+        execute(
+            &mut context,
+            &[presale_buy_ix(&launch, &buyer_key, 1, u64::MAX, PRESALE_ALLOCATION * 2)],
+            &buyer,
+            &[],
+        )
+        .await,
+        PumpCloneError::WalletNotAllowlisted.into(),
+    );
+    assert_program_error(
+        execute(
+            &mut context,
+            &[presale_buy_ix(
+                &launch,
+                &buyer_key,
+                PRESALE_ALLOCATION + 1,
+                u64::MAX,
+                PRESALE_ALLOCATION,
+            )],
+            &buyer,
+            &[],
+        )
+        .await,
+        PumpCloneError::PresaleAllocationExceeded.into(),
+    );
+    assert_program_error(
+        execute(&mut context, &[finalize_presale_ix(&launch)], &buyer, &[]).await,
+        PumpCloneError::PresaleActive.into(),
+    );
+
+    execute(
+        &mut context,
+        &[presale_buy_ix(
+            &launch,
+            &buyer_key,
+            PRESALE_ALLOCATION,
+            u64::MAX,
+            PRESALE_ALLOCATION,
+        )],
+        &buyer,
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_balance(&mut context, launch.token_account(&buyer_key)).await,
+        PRESALE_ALLOCATION
+    );
+
+    // Selling out the allocation lets anyone close the presale early
+    execute(&mut context, &[finalize_presale_ix(&launch)], &buyer, &[])
+        .await
+        .unwrap();
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert!(curve.is_presale_finalized());
+    assert_eq!(curve.presale_tokens_sold, PRESALE_ALLOCATION);
+    assert_eq!(
+        curve.virtual_token_reserves,
+        INITIAL_VIRTUAL_TOKEN_RESERVES - PRESALE_ALLOCATION
+    );
+    assert_eq!(
+        curve.virtual_sol_reserves,
+        INITIAL_VIRTUAL_SOL_RESERVES + curve.presale_sol_raised
+    );
+
+    execute(&mut context, &[buy_ix(&launch, &buyer_key, SOL_RAISED, 0)], &buyer, &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn creator_allocation_vests_linearly_after_the_cliff() {
+    const TOTAL: u64 = 1_000_000;
+
+    let launch = Launch::new();
+    let outsider = Keypair::new();
+    let beneficiary = launch.creator.pubkey();
+    let escrow = vesting_escrow(&launch);
+
+    let mut program_test = program_test(&launch);
+    add_anchor_account(
+        &mut program_test,
+        escrow,
+        &VestingEscrow {
+            mint: launch.mint,
+            beneficiary,
+            total_amount: TOTAL,
+            claimed_amount: 0,
+            start_timestamp: LATER,
+            cliff_timestamp: LATER + 100,
+            end_timestamp: LATER + 1_000,
+            bump: Pubkey::find_program_address(
+                &[b"vesting_escrow", launch.mint.as_ref()],
+                &pump_clone::ID,
+            )
+            .1,
+        },
+        VestingEscrow::LEN,
+    );
+    program_test.add_account(
+        get_associated_token_address(&escrow, &launch.mint),
+        token_account(launch.mint, escrow, TOTAL),
+    );
+    program_test.add_account(outsider.pubkey(), system_account(USER_LAMPORTS));
+    let mut context = program_test.start_with_context().await;
+
+    // Nothing unlocks before the cliff
+    set_unix_timestamp(&mut context, LATER + 50).await;
+    assert_program_error(
+        execute(&mut context, &[claim_vested_ix(&launch, &beneficiary)], &launch.creator, &[])
+            .await,
+        PumpCloneError::NothingToClaim.into(),
+    );
+
+    // Only the beneficiary can claim
+    set_unix_timestamp(&mut context, LATER + 500).await;
+    assert_program_error(
+        execute(
+            &mut context,
+            &[claim_vested_ix(&launch, &outsider.pubkey())],
+            &outsider,
+            &[],
+        )
+        .await,
+        PumpCloneError::UnauthorizedCreator.into(),
+    );
+
+    // Halfway through the schedule, half has vested
+    execute(&mut context, &[claim_vested_ix(&launch, &beneficiary)], &launch.creator, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        token_balance(&mut context, launch.token_account(&beneficiary)).await,
+        TOTAL / 2
+    );
+
+    set_unix_timestamp(&mut context, LATER + 1_000).await;
+    execute(&mut context, &[claim_vested_ix(&launch, &beneficiary)], &launch.creator, &[])
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, launch.token_account(&beneficiary)).await, TOTAL);
+    let escrow: VestingEscrow = load_anchor(&mut context, escrow).await;
+    assert_eq!(escrow.claimed_amount, TOTAL);
+}
+
+#[tokio::test]
+async fn holders_are_refunded_pro_rata_after_a_missed_deadline() {
+    let launch = Launch::new();
+    let holder = Keypair::new();
+    let holder_key = holder.pubkey();
+    let mut program_test = program_test_with_curve(&launch, |curve| {
+        curve.real_token_reserves = INITIAL_REAL_TOKEN_RESERVES - TOKENS_SOLD;
+        curve.real_sol_reserves = SOL_RAISED;
+        curve.deadline = LATER;
+    });
+    program_test.add_account(holder_key, system_account(USER_LAMPORTS));
+    program_test.add_account(
+        launch.token_account(&holder_key),
+        token_account(launch.mint, holder_key, TOKENS_SOLD),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    // Refunds only open once the deadline has passed
+    set_unix_timestamp(&mut context, LATER - 1).await;
+    assert_program_error(
+        execute(&mut context, &[refund_ix(&launch, &holder_key, TOKENS_SOLD)], &holder, &[])
+            .await,
+        PumpCloneError::CurveDeadlineNotReached.into(),
+    );
+
+    // A holder of every token sold gets back every lamport raised, in parts
+    set_unix_timestamp(&mut context, LATER).await;
+    let lamports_before = lamports(&mut context, holder_key).await;
+    for _ in 0..2 {
+        execute(
+            &mut context,
+            &[refund_ix(&launch, &holder_key, TOKENS_SOLD / 2)],
+            &launch.authority,
+            &[&holder],
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(lamports(&mut context, holder_key).await, lamports_before + SOL_RAISED);
+    assert_eq!(token_balance(&mut context, launch.token_account(&holder_key)).await, 0);
+    let curve: BondingCurve = load_zero_copy(&mut context, launch.bonding_curve).await;
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, INITIAL_REAL_TOKEN_RESERVES);
+    assert_eq!(curve.tokens_refunded, TOKENS_SOLD);
+}
+
+#[tokio::test]
+async fn only_dead_launches_can_be_closed() {
+    // A live curve can't be closed
+    let launch = Launch::new();
+    let mut context = program_test(&launch).start_with_context().await;
+    assert_program_error(
+        execute(&mut context, &[close_token_ix(&launch)], &launch.authority, &[]).await,
+        PumpCloneError::CurveNotClosable.into(),
+    );
+
+    // Once migrated, anyone can close it and the creator gets the rent back
+    let mut context = program_test_with_curve(&launch, |curve| {
+        curve.complete = 1;
+        curve.real_token_reserves = 5;
+    })
+    .start_with_context()
+    .await;
+    let closed = [
+        launch.bonding_curve,
+        launch.bonding_curve_token_account,
+        launch.token_metadata,
+        launch.price_history,
+    ];
+    let mut rent = 0;
+    for address in closed {
+        rent += lamports(&mut context, address).await;
+    }
+    let creator_before = lamports(&mut context, launch.creator.pubkey()).await;
+
+    execute(&mut context, &[close_token_ix(&launch)], &launch.authority, &[])
+        .await
+        .unwrap();
+
+    for address in closed {
+        assert!(account_is_closed(&mut context, address).await);
+    }
+    assert_eq!(lamports(&mut context, launch.creator.pubkey()).await, creator_before + rent);
+}
+
+async fn account_is_closed(context: &mut ProgramTestContext, address: Pubkey) -> bool {
+    context.banks_client.get_account(address).await.unwrap().is_none()
+}
\ No newline at end of file